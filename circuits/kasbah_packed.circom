@@ -0,0 +1,74 @@
+pragma circom 2.2.1;
+
+include "../node_modules/circomlib/circuits/bitify.circom";
+include "../node_modules/circomlib/circuits/sha256/sha256.circom";
+include "kasbah.circom";
+
+// KasbahCommitmentCircuit with its public inputs hashed into a single one, verifying a proof
+// then costs one scalar multiplication instead of one per input.
+//
+// inputs_hash = SHA-256(root || nullifier_hash || recipient || relayer || fee || refund)
+// with every input a 32 bytes big-endian field element, and the top 3 bits of the digest
+// cleared so that it fits in the BN254 scalar field.
+template KasbahPackedCommitmentCircuit(DEPTH) {
+    var NR_INPUTS = 8;
+
+    // Public Inputs
+    signal input inputs_hash;
+
+    // Private Inputs
+    signal input root;
+    signal input nullifier_hash;
+    signal input recipient[2];
+    signal input relayer[2];
+    signal input fee;
+    signal input refund;
+    signal input nullifier;
+    signal input secret;
+    signal input merkle_path[DEPTH];
+    signal input siblings[DEPTH];
+
+    component circuit = KasbahCommitmentCircuit(DEPTH);
+    circuit.root <== root;
+    circuit.nullifier_hash <== nullifier_hash;
+    circuit.recipient <== recipient;
+    circuit.relayer <== relayer;
+    circuit.fee <== fee;
+    circuit.refund <== refund;
+    circuit.nullifier <== nullifier;
+    circuit.secret <== secret;
+    circuit.merkle_path <== merkle_path;
+    circuit.siblings <== siblings;
+
+    signal inputs[NR_INPUTS];
+    inputs[0] <== root;
+    inputs[1] <== nullifier_hash;
+    inputs[2] <== recipient[0];
+    inputs[3] <== recipient[1];
+    inputs[4] <== relayer[0];
+    inputs[5] <== relayer[1];
+    inputs[6] <== fee;
+    inputs[7] <== refund;
+
+    // SHA-256 takes its input MSB first, the top 2 bits of every 32 bytes element are zero
+    component hasher = Sha256(NR_INPUTS * 256);
+    component inputBits[NR_INPUTS];
+    for (var i = 0; i < NR_INPUTS; i++) {
+        inputBits[i] = Num2Bits_strict();
+        inputBits[i].in <== inputs[i];
+
+        hasher.in[i * 256] <== 0;
+        hasher.in[i * 256 + 1] <== 0;
+        for (var j = 0; j < 254; j++) {
+            hasher.in[i * 256 + 2 + j] <== inputBits[i].out[253 - j];
+        }
+    }
+
+    // Drop the top 3 bits of the digest, Bits2Num takes its input LSB first
+    component digest = Bits2Num(253);
+    for (var i = 0; i < 253; i++) {
+        digest.in[i] <== hasher.out[255 - i];
+    }
+
+    digest.out === inputs_hash;
+}
@@ -0,0 +1,6 @@
+pragma circom 2.2.1;
+
+include "kasbah.circom";
+
+// Four commitments per pool, small enough for quick local setups and tests
+component main {public [root, nullifier_hash, recipient, relayer, fee, refund]} = KasbahCommitmentCircuit(2);
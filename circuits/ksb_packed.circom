@@ -0,0 +1,6 @@
+pragma circom 2.2.1;
+
+include "kasbah_packed.circom";
+
+// Production depth with packed public inputs
+component main {public [inputs_hash]} = KasbahPackedCommitmentCircuit(20);
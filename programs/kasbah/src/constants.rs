@@ -1,6 +1,33 @@
+use anchor_lang::prelude::Pubkey;
+
 pub const DISCRIMINATOR: usize = 8;
 pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const COMMITMENT_POOL_SEED: &[u8] = b"commitment_pool";
 pub const COMMITMENT_POOL_GENESIS_ID: u64 = 1;
-pub const MAX_COMMITMENTS_PER_POOL: u16 = 1000;
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+pub const POOL_REGISTRY_SEED: &[u8] = b"pool_registry";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const VERIFYING_KEY_SEED: &[u8] = b"verifying_key";
+/// Upper bound of the admin set stored in `Config`
+pub const MAX_ADMINS: usize = 10;
+/// Mint recorded on pools holding native lamports rather than an SPL token
+pub const NATIVE_SOL_MINT: Pubkey = Pubkey::new_from_array([0; 32]);
+/// Deepest supported merkle tree, 2^20 commitments per pool. Pools pick their depth among
+/// the ones `KasbahCommitmentCircuit` has a verifying key for
+pub const MAX_MERKLE_TREE_DEPTH: usize = 20;
+/// Upper bound of the number of recent roots a pool accepts proofs against
+pub const MAX_ROOT_HISTORY_SIZE: u8 = 100;
+/// Poseidon(nullifier, secret), big-endian BN254 field element
+pub const COMMITMENT_LEN: usize = 32;
+/// Basis points denominator, 10_000 bps = 100%
+pub const MAX_FEE_BPS: u16 = 10_000;
 pub const ZK_SEED: &[u8] = b"kasbah_zk";
+/// Compressed `proof_a || proof_b || proof_c` as emitted by the wasm `prepare_proofs` helper
+pub const GROTH16_PROOF_LEN: usize = 128;
+/// Public inputs of `KasbahCommitmentCircuit`: `root`, `nullifier_hash`, `recipient` and
+/// `relayer` as two 128-bit halves each, `fee` and `refund`
+pub const NR_PUBLIC_INPUTS: usize = 8;
+/// `vk_ic` points of a verifying key, the constant term followed by one per public input.
+/// Named so that `InitSpace` doesn't expand `NR_PUBLIC_INPUTS + 1` into `NR_PUBLIC_INPUTS + 64`
+pub const VK_IC_LEN: usize = NR_PUBLIC_INPUTS + 1;
@@ -14,4 +14,64 @@ pub enum KasbahErrors {
     InvalidNullifier,
     #[msg("Nullifier already exists")]
     DoubleSpend,
+    #[msg("Invalid redemption proof")]
+    InvalidProof,
+    #[msg("Deposit amount does not match the pool denomination")]
+    InvalidDepositAmount,
+    #[msg("Merkle root is not part of the commitment pool root history")]
+    UnknownRoot,
+    #[msg("Poseidon hashing failed")]
+    PoseidonHashFailed,
+    #[msg("Invalid root history size")]
+    InvalidRootHistorySize,
+    #[msg("Commitment pool is not full yet")]
+    CommitmentPoolNotFull,
+    #[msg("Invalid pool denomination")]
+    InvalidDenomination,
+    #[msg("Mint does not match the commitment pool")]
+    InvalidMint,
+    #[msg("Mints that withhold part of a transfer are not supported")]
+    UnsupportedMint,
+    #[msg("Signer is not the treasury authority")]
+    InvalidTreasuryAuthority,
+    #[msg("Treasury can't go below its rent-exempt balance")]
+    InsufficientTreasuryFunds,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Signer is not the pending treasury authority")]
+    InvalidPendingAuthority,
+    #[msg("Signer is not the program upgrade authority")]
+    InvalidUpgradeAuthority,
+    #[msg("Admin is already part of the admin set")]
+    AdminAlreadyExists,
+    #[msg("Admin is not part of the admin set")]
+    AdminNotFound,
+    #[msg("Admin set is full")]
+    TooManyAdmins,
+    #[msg("The last admin cannot be removed")]
+    LastAdmin,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Threshold must be between 1 and the number of admins")]
+    InvalidThreshold,
+    #[msg("Admin already approved the proposal")]
+    AlreadyApproved,
+    #[msg("Proposal does not have enough approvals")]
+    ProposalNotApproved,
+    #[msg("Proposal was already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Instruction does not match the approved proposal")]
+    ProposalMismatch,
+    #[msg("Relayer fee exceeds the pool denomination")]
+    InvalidRelayerFee,
+    #[msg("Refunds are only supported by token pools")]
+    InvalidRefund,
+    #[msg("No verifying key for this merkle tree depth")]
+    UnsupportedMerkleTreeDepth,
+    #[msg("Verifying key is frozen")]
+    VerifyingKeyFrozen,
+    #[msg("Verifying key is not frozen yet")]
+    VerifyingKeyNotFrozen,
+    #[msg("Verifying key does not match the commitment pool")]
+    InvalidVerifyingKey,
 }
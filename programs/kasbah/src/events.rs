@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{COMMITMENT_LEN, NULLIFIER_LEN};
+
+/// Commitment pools are identified by `(mint, denomination, pool_id)`
+#[event]
+pub struct PoolCreated {
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub denomination: u64,
+}
+
+/// Emitted in leaf order, enough for clients to rebuild a pool's merkle tree from logs
+#[event]
+pub struct DepositEvent {
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub denomination: u64,
+    pub leaf_index: u32,
+    pub commitment: [u8; COMMITMENT_LEN],
+    pub new_root: [u8; 32],
+}
+
+#[event]
+pub struct RedeemEvent {
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub denomination: u64,
+    pub nullifier_hash: [u8; NULLIFIER_LEN],
+    pub recipient: Pubkey,
+    pub relayer: Pubkey,
+    pub fee: u64,
+}
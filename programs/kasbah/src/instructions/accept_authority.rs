@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{KasbahErrors, Treasury, TREASURY_SEED};
+
+pub fn _accept_authority(ctx: &mut Context<AcceptAuthorityAccounts>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.authority = ctx.accounts.pending_authority.key();
+    treasury.pending_authority = None;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityAccounts<'info> {
+    pub pending_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.pending_authority == Some(pending_authority.key())
+            @ KasbahErrors::InvalidPendingAuthority,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
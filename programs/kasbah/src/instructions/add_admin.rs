@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, KasbahErrors, Proposal, ProposalAction, CONFIG_SEED, PROPOSAL_SEED};
+
+pub fn _add_admin(ctx: &mut Context<AddAdminAccounts>, input: &AddAdminInput) -> Result<()> {
+    ctx.accounts.proposal.execute(
+        &ctx.accounts.config,
+        &ProposalAction::AddAdmin(input.clone()),
+    )?;
+
+    ctx.accounts.config.add_admin(input.admin)
+}
+
+#[derive(Accounts)]
+pub struct AddAdminAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct AddAdminInput {
+    pub admin: Pubkey,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, KasbahErrors, VerifyingKeyAccount, CONFIG_SEED, VERIFYING_KEY_SEED};
+
+pub fn _append_verifying_key_ic(
+    ctx: &mut Context<AppendVerifyingKeyIcAccounts>,
+    input: &AppendVerifyingKeyIcInput,
+) -> Result<()> {
+    ctx.accounts.verifying_key.append_ic(&input.points)
+}
+
+#[derive(Accounts)]
+pub struct AppendVerifyingKeyIcAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, verifying_key.version.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AppendVerifyingKeyIcInput {
+    /// Next `vk_ic` points, in order
+    pub points: Vec<[u8; 64]>,
+}
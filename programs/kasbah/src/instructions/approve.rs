@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, KasbahErrors, Proposal, CONFIG_SEED, PROPOSAL_SEED};
+
+pub fn _approve(ctx: &mut Context<ApproveAccounts>) -> Result<()> {
+    ctx.accounts.proposal.approve(ctx.accounts.admin.key())
+}
+
+#[derive(Accounts)]
+pub struct ApproveAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    CommitmentPool, Config, KasbahErrors, PoolCreated, PoolRegistry, Proposal, ProposalAction,
+    VerifyingKeyAccount, COMMITMENT_POOL_GENESIS_ID, COMMITMENT_POOL_SEED, CONFIG_SEED,
+    DISCRIMINATOR, NATIVE_SOL_MINT, POOL_REGISTRY_SEED, PROPOSAL_SEED, VERIFYING_KEY_SEED,
+};
+
+pub fn _create_pool(ctx: &mut Context<CreatePoolAccounts>, input: &CreatePoolInput) -> Result<()> {
+    ctx.accounts.proposal.execute(
+        &ctx.accounts.config,
+        &ProposalAction::CreatePool(input.clone()),
+    )?;
+
+    require!(input.denomination > 0, KasbahErrors::InvalidDenomination);
+
+    // Token pools must point to an existing SPL or Token-2022 mint
+    match &ctx.accounts.mint {
+        Some(mint) => require_keys_eq!(mint.key(), input.mint, KasbahErrors::InvalidMint),
+        None => require_keys_eq!(input.mint, NATIVE_SOL_MINT, KasbahErrors::InvalidMint),
+    }
+
+    ctx.accounts.pool_registry.set_inner(PoolRegistry {
+        mint: input.mint,
+        denomination: input.denomination,
+        current_pool_id: COMMITMENT_POOL_GENESIS_ID,
+        merkle_tree_depth: input.merkle_tree_depth,
+        root_history_size: input.root_history_size,
+        vk_version: input.vk_version,
+        bump: ctx.bumps.pool_registry,
+    });
+
+    ctx.accounts.commitment_pool.load_init()?.init(
+        COMMITMENT_POOL_GENESIS_ID,
+        &ctx.accounts.pool_registry,
+        ctx.bumps.commitment_pool,
+    )?;
+
+    emit!(PoolCreated {
+        pool_id: COMMITMENT_POOL_GENESIS_ID,
+        mint: input.mint,
+        denomination: input.denomination,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CreatePoolInput)]
+pub struct CreatePoolAccounts<'info> {
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+    /// Omitted for native SOL pools
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, input.vk_version.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.frozen @ KasbahErrors::VerifyingKeyNotFrozen,
+        constraint = verifying_key.merkle_tree_depth == input.merkle_tree_depth
+            @ KasbahErrors::InvalidVerifyingKey,
+        constraint = verifying_key.hash() == input.vk_hash @ KasbahErrors::InvalidVerifyingKey,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR + PoolRegistry::INIT_SPACE,
+        seeds = [
+            POOL_REGISTRY_SEED,
+            input.mint.as_ref(),
+            input.denomination.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR + CommitmentPool::SPACE,
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            input.mint.as_ref(),
+            input.denomination.to_le_bytes().as_ref(),
+            COMMITMENT_POOL_GENESIS_ID.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment_pool: AccountLoader<'info, CommitmentPool>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct CreatePoolInput {
+    /// SPL or Token-2022 mint, `NATIVE_SOL_MINT` for lamports
+    pub mint: Pubkey,
+    /// Amount of every deposit and redemption in base units, e.g. 0.1, 1 or 10 SOL
+    pub denomination: u64,
+    /// Must match the depth of the circuit of `vk_version`
+    pub merkle_tree_depth: u8,
+    /// Number of recent merkle roots the pools accept proofs against
+    pub root_history_size: u8,
+    /// Frozen `VerifyingKeyAccount` the pools verify redemptions against
+    pub vk_version: u32,
+    /// `VerifyingKeyAccount::hash` of `vk_version`, approved admins vouch for the key itself
+    /// rather than whatever a single admin uploaded under that version
+    pub vk_hash: [u8; 32],
+}
@@ -1,27 +1,48 @@
-use anchor_lang::prelude::*;
-use solana_zk_sdk::encryption::{pedersen::PedersenCommitment, PEDERSEN_COMMITMENT_LEN};
-use solana_zk_token_sdk::curve25519_dalek::traits::IsIdentity;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
 
-use crate::{CommitmentPool, KasbahErrors, Treasury, COMMITMENT_POOL_SEED, TREASURY_SEED};
+use crate::{
+    CommitmentPool, Config, DepositEvent, KasbahErrors, PoolRegistry, Treasury, COMMITMENT_LEN,
+    COMMITMENT_POOL_SEED, CONFIG_SEED, NATIVE_SOL_MINT, POOL_REGISTRY_SEED, TREASURY_SEED,
+};
 
 pub fn _deposit(ctx: &mut Context<DepositAccounts>, input: &DepositInput) -> Result<()> {
-    let commitment_pool = &ctx.accounts.commitment_pool;
-    let commitment_slice = input.commitment.as_slice();
-    let commitment_bytes = [commitment_slice[0]; PEDERSEN_COMMITMENT_LEN];
-    let commitment =
-        PedersenCommitment::from_bytes(commitment_slice).ok_or(KasbahErrors::InvalidCommitment)?;
+    // The pool data is released before it gets credited through the system program
+    let event = {
+        let mut commitment_pool = ctx.accounts.commitment_pool.load_mut()?;
+        let leaf_index = commitment_pool.deposit(input.commitment, input.amount)?;
 
-    require!(!commitment_pool.is_full(), KasbahErrors::CommitmentPoolFull);
+        DepositEvent {
+            pool_id: commitment_pool.id,
+            mint: commitment_pool.mint,
+            denomination: commitment_pool.denomination,
+            leaf_index,
+            commitment: input.commitment,
+            new_root: commitment_pool.merkle_tree.root(),
+        }
+    };
 
-    // Verify commitment is valid point on curve
-    require!(
-        commitment.get_point().is_identity(),
-        KasbahErrors::InvalidCommitment
-    );
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.alice.to_account_info(),
+                to: ctx.accounts.commitment_pool.to_account_info(),
+            },
+        ),
+        input.amount,
+    )?;
 
-    ctx.accounts
-        .commitment_pool
-        .add_unique_commitment(commitment_bytes)?;
+    Treasury::collect_fee(
+        &ctx.accounts.treasury,
+        ctx.accounts.alice.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        input.amount,
+    )?;
+
+    emit!(event);
 
     Ok(())
 }
@@ -31,24 +52,46 @@ pub fn _deposit(ctx: &mut Context<DepositAccounts>, input: &DepositInput) -> Res
 pub struct DepositAccounts<'info> {
     #[account(mut)]
     pub alice: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.deposits_paused @ KasbahErrors::ProtocolPaused,
+    )]
+    pub config: Account<'info, Config>,
     #[account(
         mut,
         seeds = [TREASURY_SEED],
         bump,
     )]
     pub treasury: Account<'info, Treasury>,
+    #[account(
+        seeds = [
+            POOL_REGISTRY_SEED,
+            NATIVE_SOL_MINT.as_ref(),
+            input.amount.to_le_bytes().as_ref(),
+        ],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    /// Deposits always go to the active pool of their denomination
     #[account(
         mut,
-        seeds = [COMMITMENT_POOL_SEED, input.commitment_pool_id.to_le_bytes().as_ref()],
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            NATIVE_SOL_MINT.as_ref(),
+            input.amount.to_le_bytes().as_ref(),
+            pool_registry.current_pool_id.to_le_bytes().as_ref(),
+        ],
         bump,
     )]
-    pub commitment_pool: Account<'info, CommitmentPool>,
+    pub commitment_pool: AccountLoader<'info, CommitmentPool>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DepositInput {
-    pub commitment_pool_id: u64,
-    pub commitment: [u8; PEDERSEN_COMMITMENT_LEN],
+    /// Poseidon(nullifier, secret), big-endian
+    pub commitment: [u8; COMMITMENT_LEN],
+    /// Has to match the denomination of the pool exactly
     pub amount: u64,
 }
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    CommitmentPool, Config, DepositEvent, DepositInput, KasbahErrors, PoolRegistry, Treasury,
+    COMMITMENT_POOL_SEED, CONFIG_SEED, POOL_REGISTRY_SEED, TREASURY_SEED,
+};
+
+pub fn _deposit_token(ctx: &mut Context<DepositTokenAccounts>, input: &DepositInput) -> Result<()> {
+    let event = {
+        let mut commitment_pool = ctx.accounts.commitment_pool.load_mut()?;
+        let leaf_index = commitment_pool.deposit(input.commitment, input.amount)?;
+
+        DepositEvent {
+            pool_id: commitment_pool.id,
+            mint: commitment_pool.mint,
+            denomination: commitment_pool.denomination,
+            leaf_index,
+            commitment: input.commitment,
+            new_root: commitment_pool.merkle_tree.root(),
+        }
+    };
+
+    let vault_balance = ctx.accounts.vault.amount;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.alice_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.alice.to_account_info(),
+            },
+        ),
+        input.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Token-2022 transfer fees would leave the vault short of what it owes on redemption
+    ctx.accounts.vault.reload()?;
+    require!(
+        ctx.accounts.vault.amount == vault_balance + input.amount,
+        KasbahErrors::UnsupportedMint
+    );
+
+    Treasury::collect_fee(
+        &ctx.accounts.treasury,
+        ctx.accounts.alice.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        0,
+    )?;
+
+    emit!(event);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: DepositInput)]
+pub struct DepositTokenAccounts<'info> {
+    #[account(mut)]
+    pub alice: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.deposits_paused @ KasbahErrors::ProtocolPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = alice,
+        token::token_program = token_program,
+    )]
+    pub alice_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        seeds = [
+            POOL_REGISTRY_SEED,
+            mint.key().as_ref(),
+            input.amount.to_le_bytes().as_ref(),
+        ],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    /// Deposits always go to the active pool of their mint and denomination
+    #[account(
+        mut,
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            mint.key().as_ref(),
+            input.amount.to_le_bytes().as_ref(),
+            pool_registry.current_pool_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment_pool: AccountLoader<'info, CommitmentPool>,
+    /// Owned by the commitment pool, created by the first deposit
+    #[account(
+        init_if_needed,
+        payer = alice,
+        associated_token::mint = mint,
+        associated_token::authority = commitment_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
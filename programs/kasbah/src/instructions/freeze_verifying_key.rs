@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, KasbahErrors, VerifyingKeyAccount, CONFIG_SEED, VERIFYING_KEY_SEED};
+
+pub fn _freeze_verifying_key(ctx: &mut Context<FreezeVerifyingKeyAccounts>) -> Result<()> {
+    ctx.accounts.verifying_key.freeze()
+}
+
+#[derive(Accounts)]
+pub struct FreezeVerifyingKeyAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, verifying_key.version.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+}
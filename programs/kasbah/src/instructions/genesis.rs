@@ -1,27 +1,25 @@
 use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
 
 use crate::{
-    assert_eq_admin, CommitmentPool, KasbahErrors, Treasury, COMMITMENT_POOL_GENESIS_ID,
-    COMMITMENT_POOL_SEED, DISCRIMINATOR, TREASURY_SEED,
+    Config, KasbahErrors, Proposal, ProposalAction, Treasury, CONFIG_SEED, DISCRIMINATOR,
+    PROPOSAL_SEED, TREASURY_SEED,
 };
 
 pub fn _genesis(ctx: &mut Context<GenesisAccounts>, input: &GenesisInput) -> Result<()> {
+    ctx.accounts.proposal.execute(
+        &ctx.accounts.config,
+        &ProposalAction::Genesis(input.clone()),
+    )?;
+
     ctx.accounts.treasury.set_inner(Treasury {
         authority: input.treasury_authority,
         // 0.0001 SOL
         fixed_fee: LAMPORTS_PER_SOL / 10000,
+        fee_bps: 0,
+        pending_authority: None,
         bump: ctx.bumps.treasury,
     });
 
-    ctx.accounts.commitment_pool.set_inner(CommitmentPool {
-        id: COMMITMENT_POOL_GENESIS_ID,
-        commitments: vec![],
-        nullifiers: vec![],
-        commitment_count: 0,
-        nullifier_count: 0,
-        bump: ctx.bumps.commitment_pool,
-    });
-
     Ok(())
 }
 
@@ -30,9 +28,20 @@ pub fn _genesis(ctx: &mut Context<GenesisAccounts>, input: &GenesisInput) -> Res
 pub struct GenesisAccounts<'info> {
     #[account(
         mut,
-        constraint = assert_eq_admin(admin.key()) @ KasbahErrors::InvalidAdmin,
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
     pub system_program: Program<'info, System>,
     #[account(
         init,
@@ -42,17 +51,9 @@ pub struct GenesisAccounts<'info> {
         bump,
     )]
     pub treasury: Account<'info, Treasury>,
-    #[account(
-        init,
-        payer = admin,
-        space = DISCRIMINATOR + CommitmentPool::INIT_SPACE,
-        seeds = [COMMITMENT_POOL_SEED, COMMITMENT_POOL_GENESIS_ID.to_le_bytes().as_ref()],
-        bump,
-    )]
-    pub commitment_pool: Account<'info, CommitmentPool>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
 pub struct GenesisInput {
     pub treasury_authority: Pubkey,
 }
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Config, KasbahErrors, VerifyingKeyAccount, CONFIG_SEED, DISCRIMINATOR, VERIFYING_KEY_SEED,
+    VK_IC_LEN,
+};
+
+pub fn _init_verifying_key(
+    ctx: &mut Context<InitVerifyingKeyAccounts>,
+    input: &InitVerifyingKeyInput,
+) -> Result<()> {
+    ctx.accounts.verifying_key.set_inner(VerifyingKeyAccount {
+        version: input.version,
+        merkle_tree_depth: input.merkle_tree_depth,
+        frozen: false,
+        packed_public_inputs: input.packed_public_inputs,
+        vk_alpha_g1: input.vk_alpha_g1,
+        vk_beta_g2: input.vk_beta_g2,
+        vk_gamma_g2: input.vk_gamma_g2,
+        vk_delta_g2: input.vk_delta_g2,
+        ic_len: 0,
+        vk_ic: [[0; 64]; VK_IC_LEN],
+        bump: ctx.bumps.verifying_key,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: InitVerifyingKeyInput)]
+pub struct InitVerifyingKeyAccounts<'info> {
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR + VerifyingKeyAccount::INIT_SPACE,
+        seeds = [VERIFYING_KEY_SEED, input.version.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitVerifyingKeyInput {
+    pub version: u32,
+    pub merkle_tree_depth: u8,
+    /// Opt-in for `KasbahPackedCommitmentCircuit`, cheaper to verify
+    pub packed_public_inputs: bool,
+    pub vk_alpha_g1: [u8; 64],
+    pub vk_beta_g2: [u8; 128],
+    pub vk_gamma_g2: [u8; 128],
+    pub vk_delta_g2: [u8; 128],
+}
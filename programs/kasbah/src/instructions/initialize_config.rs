@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{program::Kasbah, Config, KasbahErrors, CONFIG_SEED, DISCRIMINATOR};
+
+pub fn _initialize_config(ctx: &mut Context<InitializeConfigAccounts>) -> Result<()> {
+    ctx.accounts.config.set_inner(Config {
+        admins: vec![ctx.accounts.upgrade_authority.key()],
+        deposits_paused: false,
+        redemptions_paused: false,
+        threshold: 1,
+        proposal_count: 0,
+        bump: ctx.bumps.config,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfigAccounts<'info> {
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ KasbahErrors::InvalidUpgradeAuthority,
+    )]
+    pub program: Program<'info, Kasbah>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key())
+            @ KasbahErrors::InvalidUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = DISCRIMINATOR + Config::INIT_SPACE,
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+}
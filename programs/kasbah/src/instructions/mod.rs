@@ -1,5 +1,65 @@
 pub mod genesis;
 pub use genesis::*;
 
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod add_admin;
+pub use add_admin::*;
+
+pub mod remove_admin;
+pub use remove_admin::*;
+
+pub mod set_threshold;
+pub use set_threshold::*;
+
+pub mod propose;
+pub use propose::*;
+
+pub mod approve;
+pub use approve::*;
+
+pub mod accept_authority;
+pub use accept_authority::*;
+
+pub mod create_pool;
+pub use create_pool::*;
+
 pub mod deposit;
 pub use deposit::*;
+
+pub mod deposit_token;
+pub use deposit_token::*;
+
+pub mod redeem;
+pub use redeem::*;
+
+pub mod redeem_token;
+pub use redeem_token::*;
+
+pub mod rollover;
+pub use rollover::*;
+
+pub mod withdraw_fees;
+pub use withdraw_fees::*;
+
+pub mod propose_authority;
+pub use propose_authority::*;
+
+pub mod set_fee;
+pub use set_fee::*;
+
+pub mod pause;
+pub use pause::*;
+
+pub mod unpause;
+pub use unpause::*;
+
+pub mod init_verifying_key;
+pub use init_verifying_key::*;
+
+pub mod append_verifying_key_ic;
+pub use append_verifying_key_ic::*;
+
+pub mod freeze_verifying_key;
+pub use freeze_verifying_key::*;
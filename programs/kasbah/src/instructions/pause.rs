@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, KasbahErrors, CONFIG_SEED};
+
+pub fn _pause(ctx: &mut Context<PauseAccounts>, input: &PauseInput) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.deposits_paused |= input.deposits;
+    config.redemptions_paused |= input.redemptions;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PauseAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+/// Selects which sides of the protocol `pause` and `unpause` toggle
+pub struct PauseInput {
+    pub deposits: bool,
+    pub redemptions: bool,
+}
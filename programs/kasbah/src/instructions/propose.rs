@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Config, KasbahErrors, Proposal, ProposalAction, CONFIG_SEED, DISCRIMINATOR, PROPOSAL_SEED,
+};
+
+pub fn _propose(ctx: &mut Context<ProposeAccounts>, input: &ProposeInput) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    ctx.accounts.proposal.set_inner(Proposal {
+        id: config.proposal_count,
+        action: input.action.clone(),
+        approvals: vec![ctx.accounts.admin.key()],
+        executed: false,
+        bump: ctx.bumps.proposal,
+    });
+
+    config.proposal_count += 1;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAccounts<'info> {
+    #[account(
+        mut,
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, config.proposal_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeInput {
+    pub action: ProposalAction,
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{KasbahErrors, Treasury, TREASURY_SEED};
+
+pub fn _propose_authority(
+    ctx: &mut Context<ProposeAuthorityAccounts>,
+    input: &ProposeAuthorityInput,
+) -> Result<()> {
+    ctx.accounts.treasury.pending_authority = Some(input.new_authority);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityAccounts<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        has_one = authority @ KasbahErrors::InvalidTreasuryAuthority,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAuthorityInput {
+    pub new_authority: Pubkey,
+}
@@ -0,0 +1,228 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+use kasbah_groth16re::{Groth16Verifyingkey, PreparedVerifyingKey};
+
+use crate::{
+    CommitmentPool, Config, KasbahErrors, Nullifier, RedeemEvent, Treasury, VerifyingKeyAccount,
+    COMMITMENT_POOL_SEED, CONFIG_SEED, DISCRIMINATOR, GROTH16_PROOF_LEN, NATIVE_SOL_MINT,
+    NR_PUBLIC_INPUTS, NULLIFIER_LEN, NULLIFIER_SEED, TREASURY_SEED, VERIFYING_KEY_SEED,
+};
+
+pub fn _redeem(ctx: &mut Context<RedeemAccounts>, input: &RedeemInput) -> Result<()> {
+    // Bob is already paid in lamports
+    require!(input.refund == 0, KasbahErrors::InvalidRefund);
+
+    let denomination = process_redemption(
+        &ctx.accounts.commitment_pool,
+        &mut ctx.accounts.nullifier,
+        ctx.bumps.nullifier,
+        &ctx.accounts.verifying_key,
+        &ctx.accounts.bob.key(),
+        &ctx.accounts.relayer.key(),
+        input,
+    )?;
+
+    Treasury::collect_fee(
+        &ctx.accounts.treasury,
+        ctx.accounts.relayer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        denomination,
+    )?;
+
+    ctx.accounts.commitment_pool.sub_lamports(denomination)?;
+    ctx.accounts
+        .bob
+        .add_lamports(denomination - input.relayer_fee)?;
+    ctx.accounts.relayer.add_lamports(input.relayer_fee)?;
+
+    emit!(RedeemEvent {
+        pool_id: input.commitment_pool_id,
+        mint: NATIVE_SOL_MINT,
+        denomination,
+        nullifier_hash: input.nullifier_hash,
+        recipient: ctx.accounts.bob.key(),
+        relayer: ctx.accounts.relayer.key(),
+        fee: input.relayer_fee,
+    });
+
+    Ok(())
+}
+
+/// Checks the root, spends the nullifier and verifies the proof is bound to `recipient`,
+/// `relayer`, the relayer fee and the refund.
+/// Returns the denomination, split between the recipient and the relayer
+pub fn process_redemption(
+    commitment_pool: &AccountLoader<CommitmentPool>,
+    nullifier: &mut Account<Nullifier>,
+    nullifier_bump: u8,
+    verifying_key: &VerifyingKeyAccount,
+    recipient: &Pubkey,
+    relayer: &Pubkey,
+    input: &RedeemInput,
+) -> Result<u64> {
+    let denomination = {
+        let commitment_pool = commitment_pool.load()?;
+        require!(
+            commitment_pool.merkle_tree.is_known_root(&input.root),
+            KasbahErrors::UnknownRoot
+        );
+        require!(
+            commitment_pool.vk_version == verifying_key.version,
+            KasbahErrors::InvalidVerifyingKey
+        );
+        commitment_pool.denomination
+    };
+
+    require!(
+        input.relayer_fee <= denomination,
+        KasbahErrors::InvalidRelayerFee
+    );
+
+    nullifier.spend(input.commitment_pool_id, nullifier_bump)?;
+
+    let [recipient_hi, recipient_lo] = pubkey_to_field_elements(recipient);
+    let [relayer_hi, relayer_lo] = pubkey_to_field_elements(relayer);
+    let public_inputs = [
+        input.root,
+        input.nullifier_hash,
+        recipient_hi,
+        recipient_lo,
+        relayer_hi,
+        relayer_lo,
+        u64_to_field_element(input.relayer_fee),
+        u64_to_field_element(input.refund),
+    ];
+
+    let groth16_verifying_key = verifying_key.to_groth16_verifyingkey();
+    if verifying_key.packed_public_inputs {
+        verify_proof(
+            &input.proof,
+            &[pack_public_inputs(&public_inputs)],
+            &groth16_verifying_key,
+        )?;
+    } else {
+        verify_proof(&input.proof, &public_inputs, &groth16_verifying_key)?;
+    }
+
+    Ok(denomination)
+}
+
+/// Single public input of the packed circuits, SHA-256 of the big-endian public inputs with
+/// the top 3 bits cleared so that it fits in the BN254 scalar field
+pub fn pack_public_inputs(public_inputs: &[[u8; 32]; NR_PUBLIC_INPUTS]) -> [u8; 32] {
+    let public_inputs: Vec<&[u8]> = public_inputs.iter().map(|input| input.as_slice()).collect();
+    let mut packed = hashv(&public_inputs).to_bytes();
+    packed[0] &= 0b0001_1111;
+
+    packed
+}
+
+/// Splits a pubkey into two big-endian field elements holding its high and low 16 bytes,
+/// 32 bytes don't always fit in the BN254 scalar field
+pub fn pubkey_to_field_elements(key: &Pubkey) -> [[u8; 32]; 2] {
+    let mut hi = [0u8; 32];
+    let mut lo = [0u8; 32];
+    hi[16..].copy_from_slice(&key.as_ref()[..16]);
+    lo[16..].copy_from_slice(&key.as_ref()[16..]);
+
+    [hi, lo]
+}
+
+pub fn u64_to_field_element(value: u64) -> [u8; 32] {
+    let mut element = [0u8; 32];
+    element[24..].copy_from_slice(&value.to_be_bytes());
+
+    element
+}
+
+/// Verify a `prepare_proofs` encoded proof against `KasbahCommitmentCircuit`'s public inputs,
+/// or the packed one of `KasbahPackedCommitmentCircuit`
+pub fn verify_proof<const N: usize>(
+    proof: &[u8; GROTH16_PROOF_LEN],
+    public_inputs: &[[u8; 32]; N],
+    verifying_key: &Groth16Verifyingkey,
+) -> Result<()> {
+    let proof_a = proof[0..32].try_into().unwrap();
+    let proof_b = proof[32..96].try_into().unwrap();
+    let proof_c = proof[96..128].try_into().unwrap();
+
+    // Points were validated when the key was frozen
+    let prepared_verifying_key =
+        PreparedVerifyingKey::<N>::new_unchecked(verifying_key).map_err(|e| {
+            msg!("Invalid verifying key: {}", e);
+            KasbahErrors::InvalidVerifyingKey
+        })?;
+
+    prepared_verifying_key
+        .verify_compressed(proof_a, proof_b, proof_c, public_inputs)
+        .map_err(|e| {
+            msg!("Proof verification failed: {}", e);
+            KasbahErrors::InvalidProof
+        })?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RedeemInput)]
+pub struct RedeemAccounts<'info> {
+    /// Submits the transaction and pays its costs, bob himself when not relayed
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// Recipient bound into the proof
+    #[account(mut)]
+    pub bob: SystemAccount<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.redemptions_paused @ KasbahErrors::ProtocolPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            NATIVE_SOL_MINT.as_ref(),
+            input.denomination.to_le_bytes().as_ref(),
+            input.commitment_pool_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment_pool: AccountLoader<'info, CommitmentPool>,
+    /// Must be the version the commitment pool was created under
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, verifying_key.version.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.frozen @ KasbahErrors::VerifyingKeyNotFrozen,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+    /// Created on first redemption, a spent nullifier fails with `DoubleSpend`
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = DISCRIMINATOR + Nullifier::INIT_SPACE,
+        seeds = [NULLIFIER_SEED, input.nullifier_hash.as_ref()],
+        bump,
+    )]
+    pub nullifier: Account<'info, Nullifier>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RedeemInput {
+    pub denomination: u64,
+    pub commitment_pool_id: u64,
+    /// Compressed `proof_a` (negated) || `proof_b` || `proof_c`, big-endian
+    pub proof: [u8; GROTH16_PROOF_LEN],
+    pub root: [u8; 32],
+    pub nullifier_hash: [u8; NULLIFIER_LEN],
+    /// Paid to the relayer out of the denomination
+    pub relayer_fee: u64,
+    /// Lamports the relayer sends to bob alongside token redemptions, 0 for native SOL pools
+    pub refund: u64,
+}
@@ -0,0 +1,178 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    process_redemption, CommitmentPool, Config, KasbahErrors, Nullifier, RedeemEvent, RedeemInput,
+    Treasury, VerifyingKeyAccount, COMMITMENT_POOL_SEED, CONFIG_SEED, DISCRIMINATOR,
+    NULLIFIER_SEED, TREASURY_SEED, VERIFYING_KEY_SEED,
+};
+
+pub fn _redeem_token(ctx: &mut Context<RedeemTokenAccounts>, input: &RedeemInput) -> Result<()> {
+    let denomination = process_redemption(
+        &ctx.accounts.commitment_pool,
+        &mut ctx.accounts.nullifier,
+        ctx.bumps.nullifier,
+        &ctx.accounts.verifying_key,
+        &ctx.accounts.bob.key(),
+        &ctx.accounts.relayer.key(),
+        input,
+    )?;
+
+    Treasury::collect_fee(
+        &ctx.accounts.treasury,
+        ctx.accounts.relayer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        0,
+    )?;
+
+    let mint = ctx.accounts.mint.key();
+    let denomination_bytes = input.denomination.to_le_bytes();
+    let commitment_pool_id_bytes = input.commitment_pool_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        COMMITMENT_POOL_SEED,
+        mint.as_ref(),
+        denomination_bytes.as_ref(),
+        commitment_pool_id_bytes.as_ref(),
+        &[ctx.bumps.commitment_pool],
+    ]];
+
+    let payouts = [
+        (
+            ctx.accounts.bob_token_account.to_account_info(),
+            denomination - input.relayer_fee,
+        ),
+        (
+            ctx.accounts.relayer_token_account.to_account_info(),
+            input.relayer_fee,
+        ),
+    ];
+    for (to, amount) in payouts {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.commitment_pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    // Lets bob pay for his next transactions without funding his wallet
+    if input.refund > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.relayer.to_account_info(),
+                    to: ctx.accounts.bob.to_account_info(),
+                },
+            ),
+            input.refund,
+        )?;
+    }
+
+    emit!(RedeemEvent {
+        pool_id: input.commitment_pool_id,
+        mint,
+        denomination,
+        nullifier_hash: input.nullifier_hash,
+        recipient: ctx.accounts.bob.key(),
+        relayer: ctx.accounts.relayer.key(),
+        fee: input.relayer_fee,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: RedeemInput)]
+pub struct RedeemTokenAccounts<'info> {
+    /// Submits the transaction and pays its costs, bob himself when not relayed
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// Recipient bound into the proof
+    #[account(mut)]
+    pub bob: SystemAccount<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.redemptions_paused @ KasbahErrors::ProtocolPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = mint,
+        associated_token::authority = bob,
+        associated_token::token_program = token_program,
+    )]
+    pub bob_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = mint,
+        associated_token::authority = relayer,
+        associated_token::token_program = token_program,
+    )]
+    pub relayer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            mint.key().as_ref(),
+            input.denomination.to_le_bytes().as_ref(),
+            input.commitment_pool_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment_pool: AccountLoader<'info, CommitmentPool>,
+    /// Must be the version the commitment pool was created under
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, verifying_key.version.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.frozen @ KasbahErrors::VerifyingKeyNotFrozen,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = commitment_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// Created on first redemption, a spent nullifier fails with `DoubleSpend`
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = DISCRIMINATOR + Nullifier::INIT_SPACE,
+        seeds = [NULLIFIER_SEED, input.nullifier_hash.as_ref()],
+        bump,
+    )]
+    pub nullifier: Account<'info, Nullifier>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
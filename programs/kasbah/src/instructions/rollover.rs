@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    CommitmentPool, Config, KasbahErrors, PoolCreated, PoolRegistry, COMMITMENT_POOL_SEED,
+    CONFIG_SEED, DISCRIMINATOR, POOL_REGISTRY_SEED,
+};
+
+pub fn _rollover(ctx: &mut Context<RolloverAccounts>) -> Result<()> {
+    require!(
+        ctx.accounts.commitment_pool.load()?.is_full(),
+        KasbahErrors::CommitmentPoolNotFull
+    );
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    let next_pool_id = pool_registry.current_pool_id + 1;
+
+    ctx.accounts.next_commitment_pool.load_init()?.init(
+        next_pool_id,
+        pool_registry,
+        ctx.bumps.next_commitment_pool,
+    )?;
+
+    pool_registry.current_pool_id = next_pool_id;
+
+    emit!(PoolCreated {
+        pool_id: next_pool_id,
+        mint: pool_registry.mint,
+        denomination: pool_registry.denomination,
+    });
+
+    Ok(())
+}
+
+/// Permissionless, whoever hits a full pool pays for the rent of the next one
+#[derive(Accounts)]
+pub struct RolloverAccounts<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.deposits_paused @ KasbahErrors::ProtocolPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            POOL_REGISTRY_SEED,
+            pool_registry.mint.as_ref(),
+            pool_registry.denomination.to_le_bytes().as_ref(),
+        ],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    #[account(
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            pool_registry.mint.as_ref(),
+            pool_registry.denomination.to_le_bytes().as_ref(),
+            pool_registry.current_pool_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment_pool: AccountLoader<'info, CommitmentPool>,
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + CommitmentPool::SPACE,
+        seeds = [
+            COMMITMENT_POOL_SEED,
+            pool_registry.mint.as_ref(),
+            pool_registry.denomination.to_le_bytes().as_ref(),
+            (pool_registry.current_pool_id + 1).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub next_commitment_pool: AccountLoader<'info, CommitmentPool>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Config, KasbahErrors, Proposal, ProposalAction, Treasury, CONFIG_SEED, MAX_FEE_BPS,
+    PROPOSAL_SEED, TREASURY_SEED,
+};
+
+pub fn _set_fee(ctx: &mut Context<SetFeeAccounts>, input: &SetFeeInput) -> Result<()> {
+    ctx.accounts
+        .proposal
+        .execute(&ctx.accounts.config, &ProposalAction::SetFee(input.clone()))?;
+
+    require!(input.fee_bps <= MAX_FEE_BPS, KasbahErrors::InvalidFee);
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.fixed_fee = input.fixed_fee;
+    treasury.fee_bps = input.fee_bps;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct SetFeeInput {
+    /// Lamports charged on every deposit and redemption
+    pub fixed_fee: u64,
+    /// Charged on top of the fixed fee by native SOL pools
+    pub fee_bps: u16,
+}
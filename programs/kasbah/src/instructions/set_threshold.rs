@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, KasbahErrors, Proposal, ProposalAction, CONFIG_SEED, PROPOSAL_SEED};
+
+pub fn _set_threshold(
+    ctx: &mut Context<SetThresholdAccounts>,
+    input: &SetThresholdInput,
+) -> Result<()> {
+    ctx.accounts.proposal.execute(
+        &ctx.accounts.config,
+        &ProposalAction::SetThreshold(input.clone()),
+    )?;
+
+    ctx.accounts.config.set_threshold(input.threshold)
+}
+
+#[derive(Accounts)]
+pub struct SetThresholdAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct SetThresholdInput {
+    pub threshold: u8,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+use crate::{PauseAccounts, PauseInput};
+
+pub fn _unpause(ctx: &mut Context<PauseAccounts>, input: &PauseInput) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.deposits_paused &= !input.deposits;
+    config.redemptions_paused &= !input.redemptions;
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Config, KasbahErrors, Proposal, ProposalAction, Treasury, CONFIG_SEED, PROPOSAL_SEED,
+    TREASURY_SEED,
+};
+
+pub fn _withdraw_fees(
+    ctx: &mut Context<WithdrawFeesAccounts>,
+    input: &WithdrawFeesInput,
+) -> Result<()> {
+    ctx.accounts.proposal.execute(
+        &ctx.accounts.config,
+        &ProposalAction::WithdrawFees(input.clone()),
+    )?;
+
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_balance = Rent::get()?.minimum_balance(treasury.data_len());
+    let withdrawable = treasury.lamports().saturating_sub(rent_exempt_balance);
+
+    require!(
+        input.amount <= withdrawable,
+        KasbahErrors::InsufficientTreasuryFunds
+    );
+
+    ctx.accounts.treasury.sub_lamports(input.amount)?;
+    ctx.accounts.receiver.add_lamports(input.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFeesAccounts<'info> {
+    #[account(
+        constraint = config.is_admin(&admin.key()) @ KasbahErrors::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    /// Fees are always paid out to the treasury authority
+    #[account(
+        mut,
+        address = treasury.authority @ KasbahErrors::InvalidTreasuryAuthority,
+    )]
+    pub receiver: SystemAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct WithdrawFeesInput {
+    pub amount: u64,
+}
@@ -3,57 +3,156 @@
 use anchor_lang::prelude::*;
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod sdk;
 pub mod state;
 
 pub use constants::*;
 pub use errors::*;
+pub use events::*;
 pub use instructions::*;
 pub use sdk::*;
 pub use state::*;
 
 declare_id!("ksb1AcDWRRawr7Amf9H7wCGtYvyVGNfbMvBPVAV6BJT");
 
-pub mod admin {
+#[program]
+pub mod kasbah {
     use super::*;
-    use anchor_lang::solana_program::pubkey;
 
-    #[cfg(feature = "localnet")]
-    pub const ADMINS: [Pubkey; 1] = [pubkey!("ALAAqK8zJkFsU2FmzzBypJZmJngBuQ6ayHeR2cHsTJN1")];
+    /// Bootstrap the admin set, signed by the program upgrade authority
+    pub fn initialize_config(mut ctx: Context<InitializeConfigAccounts>) -> Result<()> {
+        _initialize_config(&mut ctx)
+    }
 
-    #[cfg(not(feature = "localnet"))]
-    pub const ADMINS: [Pubkey; 1] = [pubkey!("ALAAqK8zJkFsU2FmzzBypJZmJngBuQ6ayHeR2cHsTJN1")];
-}
+    /// Open a proposal for an admin action, approved by the proposer
+    pub fn propose(mut ctx: Context<ProposeAccounts>, input: ProposeInput) -> Result<()> {
+        _propose(&mut ctx, &input)
+    }
 
-pub fn assert_eq_admin(admin: Pubkey) -> bool {
-    crate::admin::ADMINS
-        .iter()
-        .any(|predefined_admin| predefined_admin.eq(&admin))
-}
+    /// Approve a pending proposal
+    pub fn approve(mut ctx: Context<ApproveAccounts>) -> Result<()> {
+        _approve(&mut ctx)
+    }
 
-#[program]
-pub mod kasbah {
-    use super::*;
+    /// Update the number of approvals proposals need, once approved
+    pub fn set_threshold(
+        mut ctx: Context<SetThresholdAccounts>,
+        input: SetThresholdInput,
+    ) -> Result<()> {
+        _set_threshold(&mut ctx, &input)
+    }
 
-    /// Initialize the Kasbah protocol, create the fees vault and the initial commitment pool
+    /// Add an admin to the admin set, once approved
+    pub fn add_admin(mut ctx: Context<AddAdminAccounts>, input: AddAdminInput) -> Result<()> {
+        _add_admin(&mut ctx, &input)
+    }
+
+    /// Remove an admin from the admin set, the last admin cannot be removed
+    pub fn remove_admin(
+        mut ctx: Context<RemoveAdminAccounts>,
+        input: RemoveAdminInput,
+    ) -> Result<()> {
+        _remove_admin(&mut ctx, &input)
+    }
+
+    /// Halt deposits and/or redemptions
+    pub fn pause(mut ctx: Context<PauseAccounts>, input: PauseInput) -> Result<()> {
+        _pause(&mut ctx, &input)
+    }
+
+    /// Resume deposits and/or redemptions
+    pub fn unpause(mut ctx: Context<PauseAccounts>, input: PauseInput) -> Result<()> {
+        _unpause(&mut ctx, &input)
+    }
+
+    /// Create a verifying key account, its `vk_ic` points are appended separately
+    pub fn init_verifying_key(
+        mut ctx: Context<InitVerifyingKeyAccounts>,
+        input: InitVerifyingKeyInput,
+    ) -> Result<()> {
+        _init_verifying_key(&mut ctx, &input)
+    }
+
+    /// Append `vk_ic` points to a verifying key that is not frozen yet
+    pub fn append_verifying_key_ic(
+        mut ctx: Context<AppendVerifyingKeyIcAccounts>,
+        input: AppendVerifyingKeyIcInput,
+    ) -> Result<()> {
+        _append_verifying_key_ic(&mut ctx, &input)
+    }
+
+    /// Freeze a complete verifying key, pools can only be created under frozen keys
+    pub fn freeze_verifying_key(mut ctx: Context<FreezeVerifyingKeyAccounts>) -> Result<()> {
+        _freeze_verifying_key(&mut ctx)
+    }
+
+    /// Initialize the Kasbah protocol and create the fees vault
     pub fn genesis(mut ctx: Context<GenesisAccounts>, input: GenesisInput) -> Result<()> {
         _genesis(&mut ctx, &input)
     }
 
-    // /**
-    //  * Deposit KSB confidential tokens into the pool and mint a receipt for redemption
-    //  */
-    // pub fn deposit(ctx: Context<DepositAccounts>, input: DepositInput) -> Result<()> {
-    //     _deposit(ctx, input)
-    // }
+    /// Create the registry and the initial commitment pool of a new denomination
+    pub fn create_pool(mut ctx: Context<CreatePoolAccounts>, input: CreatePoolInput) -> Result<()> {
+        _create_pool(&mut ctx, &input)
+    }
+
+    /// Deposit the pool denomination along with a commitment, paying the protocol fee to the treasury
+    pub fn deposit(mut ctx: Context<DepositAccounts>, input: DepositInput) -> Result<()> {
+        _deposit(&mut ctx, &input)
+    }
 
-    // /**
-    //  * Redeem a receipt for KSB confidential tokens
-    //  */
-    // pub fn redeem(ctx: Context<RedeemAccounts>, input: RedeemInput) -> Result<()> {
-    //     _redeem(ctx, input)
-    // }
+    /// Deposit the denomination of an SPL or Token-2022 pool into its vault, along with a commitment
+    pub fn deposit_token(
+        mut ctx: Context<DepositTokenAccounts>,
+        input: DepositInput,
+    ) -> Result<()> {
+        _deposit_token(&mut ctx, &input)
+    }
+
+    /// Redeem a commitment by proving membership in the pool, paying the denomination to bob minus
+    /// the relayer fee
+    pub fn redeem(mut ctx: Context<RedeemAccounts>, input: RedeemInput) -> Result<()> {
+        _redeem(&mut ctx, &input)
+    }
+
+    /// Redeem a commitment of an SPL or Token-2022 pool, paying bob and the relayer out of the
+    /// pool vault
+    pub fn redeem_token(mut ctx: Context<RedeemTokenAccounts>, input: RedeemInput) -> Result<()> {
+        _redeem_token(&mut ctx, &input)
+    }
+
+    /// Withdraw collected protocol fees to the treasury authority, keeping the treasury rent-exempt
+    pub fn withdraw_fees(
+        mut ctx: Context<WithdrawFeesAccounts>,
+        input: WithdrawFeesInput,
+    ) -> Result<()> {
+        _withdraw_fees(&mut ctx, &input)
+    }
+
+    /// Update the fixed and basis points protocol fees, once approved
+    pub fn set_fee(mut ctx: Context<SetFeeAccounts>, input: SetFeeInput) -> Result<()> {
+        _set_fee(&mut ctx, &input)
+    }
+
+    /// First step of the treasury authority handover, nominate the next authority
+    pub fn propose_authority(
+        mut ctx: Context<ProposeAuthorityAccounts>,
+        input: ProposeAuthorityInput,
+    ) -> Result<()> {
+        _propose_authority(&mut ctx, &input)
+    }
+
+    /// Second step of the treasury authority handover, signed by the nominated authority
+    pub fn accept_authority(mut ctx: Context<AcceptAuthorityAccounts>) -> Result<()> {
+        _accept_authority(&mut ctx)
+    }
+
+    /// Create the next commitment pool once the active one is full, and route deposits to it
+    pub fn rollover(mut ctx: Context<RolloverAccounts>) -> Result<()> {
+        _rollover(&mut ctx)
+    }
 }
 
 #[cfg(test)]
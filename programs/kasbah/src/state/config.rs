@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{KasbahErrors, MAX_ADMINS};
+
+#[account]
+#[derive(InitSpace)]
+/// Global protocol configuration, bootstrapped once by the program upgrade authority
+pub struct Config {
+    #[max_len(MAX_ADMINS)]
+    pub admins: Vec<Pubkey>,
+    /// Halts deposits and rollovers
+    pub deposits_paused: bool,
+    /// Halts redemptions
+    pub redemptions_paused: bool,
+    /// Number of admin approvals a proposal needs before it can be executed
+    pub threshold: u8,
+    /// Id of the next proposal
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl Config {
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admins.contains(key)
+    }
+
+    pub fn add_admin(&mut self, admin: Pubkey) -> Result<()> {
+        require!(!self.is_admin(&admin), KasbahErrors::AdminAlreadyExists);
+        require!(self.admins.len() < MAX_ADMINS, KasbahErrors::TooManyAdmins);
+
+        self.admins.push(admin);
+
+        Ok(())
+    }
+
+    pub fn remove_admin(&mut self, admin: &Pubkey) -> Result<()> {
+        let index = self
+            .admins
+            .iter()
+            .position(|existing| existing == admin)
+            .ok_or(KasbahErrors::AdminNotFound)?;
+        require!(self.admins.len() > 1, KasbahErrors::LastAdmin);
+        require!(
+            self.admins.len() > self.threshold as usize,
+            KasbahErrors::InvalidThreshold
+        );
+
+        self.admins.swap_remove(index);
+
+        Ok(())
+    }
+
+    pub fn set_threshold(&mut self, threshold: u8) -> Result<()> {
+        require!(
+            threshold > 0 && threshold as usize <= self.admins.len(),
+            KasbahErrors::InvalidThreshold
+        );
+
+        self.threshold = threshold;
+
+        Ok(())
+    }
+}
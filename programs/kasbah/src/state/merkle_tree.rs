@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use solana_poseidon::{hashv, Endianness, Parameters};
+
+use crate::{KasbahErrors, COMMITMENT_LEN, MAX_MERKLE_TREE_DEPTH, MAX_ROOT_HISTORY_SIZE};
+
+/// Value of an empty leaf
+pub const ZERO_VALUE: [u8; COMMITMENT_LEN] = [0; COMMITMENT_LEN];
+
+#[zero_copy]
+/// Append-only Poseidon merkle tree, compatible with `KasbahCommitmentCircuit`.
+///
+/// Only the last left node of every level is kept around, which is enough to
+/// append a leaf and recompute the root with `depth` hashes. Levels past `depth` are unused.
+///
+/// The last `root_history_size` roots are kept in a ring buffer, so proofs generated against
+/// a slightly outdated root are still accepted after concurrent deposits.
+pub struct MerkleTree {
+    /// Roots of empty subtrees, `zeros[0]` being an empty leaf
+    pub zeros: [[u8; 32]; MAX_MERKLE_TREE_DEPTH],
+    pub filled_subtrees: [[u8; 32]; MAX_MERKLE_TREE_DEPTH],
+    pub roots: [[u8; 32]; MAX_ROOT_HISTORY_SIZE as usize],
+    pub next_index: u32,
+    pub root_history_size: u8,
+    pub current_root_index: u8,
+    /// Must match the depth of the circuit proofs are verified against
+    pub depth: u8,
+    _padding: [u8; 1],
+}
+
+impl MerkleTree {
+    /// Initializes a zeroed tree in place, the account is too large to be built on the stack
+    pub fn init(&mut self, depth: u8, root_history_size: u8) -> Result<()> {
+        require!(
+            depth > 0 && depth as usize <= MAX_MERKLE_TREE_DEPTH,
+            KasbahErrors::UnsupportedMerkleTreeDepth
+        );
+        require!(
+            root_history_size > 0 && root_history_size <= MAX_ROOT_HISTORY_SIZE,
+            KasbahErrors::InvalidRootHistorySize
+        );
+
+        let depth = depth as usize;
+        self.zeros[0] = ZERO_VALUE;
+        for level in 1..depth {
+            self.zeros[level] = hash_pair(&self.zeros[level - 1], &self.zeros[level - 1])?;
+        }
+        self.filled_subtrees = self.zeros;
+
+        self.roots[0] = hash_pair(&self.zeros[depth - 1], &self.zeros[depth - 1])?;
+        self.depth = depth as u8;
+        self.root_history_size = root_history_size;
+        self.current_root_index = 0;
+        self.next_index = 0;
+
+        Ok(())
+    }
+
+    /// Appends a leaf and returns its index.
+    ///
+    /// The index bits, from the leaf up, are the `merkle_path` expected by the circuit
+    /// (which takes it top-to-bottom).
+    pub fn append(&mut self, leaf: [u8; COMMITMENT_LEN]) -> Result<u32> {
+        require!(!self.is_full(), KasbahErrors::CommitmentPoolFull);
+
+        let leaf_index = self.next_index;
+        let mut current_index = leaf_index;
+        let mut current_hash = leaf;
+
+        for level in 0..self.depth as usize {
+            let (left, right) = if current_index % 2 == 0 {
+                self.filled_subtrees[level] = current_hash;
+                (current_hash, self.zeros[level])
+            } else {
+                (self.filled_subtrees[level], current_hash)
+            };
+            current_hash = hash_pair(&left, &right)?;
+            current_index /= 2;
+        }
+
+        self.current_root_index = (self.current_root_index + 1) % self.root_history_size;
+        self.roots[self.current_root_index as usize] = current_hash;
+        self.next_index += 1;
+
+        Ok(leaf_index)
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.roots[self.current_root_index as usize]
+    }
+
+    /// Walks the root history, newest first
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        // Unused slots of the ring buffer are zeroed, zero is never a valid root
+        if *root == [0u8; 32] {
+            return false;
+        }
+
+        let len = self.root_history_size as usize;
+        (0..len)
+            .map(|i| (self.current_root_index as usize + len - i) % len)
+            .any(|i| self.roots[i] == *root)
+    }
+
+    pub fn capacity(&self) -> u32 {
+        1 << self.depth
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.next_index >= self.capacity()
+    }
+}
+
+/// Circom compatible Poseidon(left, right), uses the syscall on-chain and light-poseidon off-chain
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> Result<[u8; 32]> {
+    hashv(
+        Parameters::Bn254X5,
+        Endianness::BigEndian,
+        &[left.as_slice(), right.as_slice()],
+    )
+    .map(|hash| hash.to_bytes())
+    .map_err(|_| KasbahErrors::PoseidonHashFailed.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_tree(depth: u8, root_history_size: u8) -> MerkleTree {
+        let mut tree = MerkleTree {
+            zeros: [[0; 32]; MAX_MERKLE_TREE_DEPTH],
+            filled_subtrees: [[0; 32]; MAX_MERKLE_TREE_DEPTH],
+            roots: [[0; 32]; MAX_ROOT_HISTORY_SIZE as usize],
+            next_index: 0,
+            root_history_size: 0,
+            current_root_index: 0,
+            depth: 0,
+            _padding: [0; 1],
+        };
+        tree.init(depth, root_history_size).unwrap();
+        tree
+    }
+
+    fn leaf(byte: u8) -> [u8; 32] {
+        let mut leaf = [0u8; 32];
+        leaf[31] = byte;
+        leaf
+    }
+
+    #[test]
+    fn test_empty_root() {
+        let tree = new_tree(2, 1);
+        let empty_node = hash_pair(&ZERO_VALUE, &ZERO_VALUE).unwrap();
+
+        assert_eq!(tree.root(), hash_pair(&empty_node, &empty_node).unwrap());
+    }
+
+    #[test]
+    fn test_production_depth() {
+        let mut tree = new_tree(MAX_MERKLE_TREE_DEPTH as u8, 1);
+        assert_eq!(tree.capacity(), 1 << 20);
+
+        let mut empty_root = ZERO_VALUE;
+        for _ in 0..MAX_MERKLE_TREE_DEPTH {
+            empty_root = hash_pair(&empty_root, &empty_root).unwrap();
+        }
+        assert_eq!(tree.root(), empty_root);
+
+        tree.append(leaf(1)).unwrap();
+        let mut root = leaf(1);
+        for level in 0..MAX_MERKLE_TREE_DEPTH {
+            root = hash_pair(&root, &tree.zeros[level]).unwrap();
+        }
+        assert_eq!(tree.root(), root);
+    }
+
+    #[test]
+    fn test_append_until_full() {
+        let mut tree = new_tree(2, 1);
+        let leaves: Vec<[u8; 32]> = (1..=4).map(leaf).collect();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(tree.append(*leaf).unwrap(), i as u32);
+        }
+
+        let left = hash_pair(&leaves[0], &leaves[1]).unwrap();
+        let right = hash_pair(&leaves[2], &leaves[3]).unwrap();
+        assert_eq!(tree.root(), hash_pair(&left, &right).unwrap());
+        assert!(tree.is_full());
+        assert!(tree.append(leaf(5)).is_err());
+    }
+
+    #[test]
+    fn test_root_history() {
+        let mut tree = new_tree(2, 2);
+        let empty_root = tree.root();
+
+        tree.append(leaf(1)).unwrap();
+        let first_root = tree.root();
+        assert!(tree.is_known_root(&empty_root));
+        assert!(tree.is_known_root(&first_root));
+
+        // The ring buffer only holds two roots, the empty one gets evicted
+        tree.append(leaf(2)).unwrap();
+        assert!(!tree.is_known_root(&empty_root));
+        assert!(tree.is_known_root(&first_root));
+        assert!(tree.is_known_root(&tree.root()));
+        assert!(!tree.is_known_root(&[0u8; 32]));
+    }
+}
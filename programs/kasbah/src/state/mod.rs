@@ -1,5 +1,23 @@
+pub mod config;
+pub use config::*;
+
+pub mod merkle_tree;
+pub use merkle_tree::*;
+
+pub mod nullifier;
+pub use nullifier::*;
+
 pub mod pool;
 pub use pool::*;
 
+pub mod proposal;
+pub use proposal::*;
+
+pub mod registry;
+pub use registry::*;
+
 pub mod treasury;
 pub use treasury::*;
+
+pub mod verifying_key;
+pub use verifying_key::*;
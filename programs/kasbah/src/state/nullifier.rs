@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::KasbahErrors;
+
+#[account]
+#[derive(InitSpace)]
+/// One account per spent nullifier hash, seeded by it.
+/// Double-spend checks are a single PDA lookup no matter how many redemptions happened.
+pub struct Nullifier {
+    pub spent: bool,
+    /// Commitment pool the nullifier was redeemed from
+    pub pool_id: u64,
+    pub bump: u8,
+}
+
+impl Nullifier {
+    pub fn spend(&mut self, pool_id: u64, bump: u8) -> Result<()> {
+        require!(!self.spent, KasbahErrors::DoubleSpend);
+
+        self.spent = true;
+        self.pool_id = pool_id;
+        self.bump = bump;
+
+        Ok(())
+    }
+}
@@ -1,48 +1,66 @@
 use anchor_lang::prelude::*;
-use solana_zk_sdk::encryption::PEDERSEN_COMMITMENT_LEN;
 
-use crate::{KasbahErrors, MAX_COMMITMENTS_PER_POOL, NULLIFIER_LEN};
+use kasbah_groth16re::is_less_than_bn254_field_size_be;
 
-#[account]
-#[derive(InitSpace)]
+use crate::{KasbahErrors, MerkleTree, PoolRegistry, COMMITMENT_LEN};
+
+#[account(zero_copy)]
 /// The commitment pool is an indexed collection of all commitments.
-/// The dApp holds multiple pools that gets created sequentially when a commitment pool is
-/// close to be full.
+/// The dApp holds multiple pools that gets created sequentially, deposits go to the pool
+/// tracked by `PoolRegistry` until it's full and gets rolled over to `id + 1`.
+///
+/// Leaves are not stored, only what is needed to append to the merkle tree. The account
+/// size doesn't depend on the pool capacity, and neither does the cost of a deposit.
+/// Commitments can be recovered from the deposit instructions.
 pub struct CommitmentPool {
     pub id: u64,
-    #[max_len(MAX_COMMITMENTS_PER_POOL)]
-    pub commitments: Vec<[u8; PEDERSEN_COMMITMENT_LEN]>, // compressed Pedersen commitments
-    #[max_len(MAX_COMMITMENTS_PER_POOL)]
-    pub nullifiers: Vec<[u8; NULLIFIER_LEN]>, // Hashed secrets: H(secret || recipient_pubkey)
-    pub commitment_count: u16,
-    pub nullifier_count: u16,
+    /// Every deposit and redemption moves exactly this amount, so that all commitments
+    /// of a pool are indistinguishable
+    pub denomination: u64,
+    /// SPL or Token-2022 mint held in the pool vault, `NATIVE_SOL_MINT` for lamports
+    pub mint: Pubkey,
+    /// Merkle tree of all commitments, redemptions prove membership against its roots
+    pub merkle_tree: MerkleTree,
+    /// Version of the `VerifyingKeyAccount` redemptions are verified against
+    pub vk_version: u32,
     pub bump: u8,
+    _padding: [u8; 3],
 }
 
 impl CommitmentPool {
-    /// 20.000 CUs worst case
-    pub fn add_unique_commitment(
-        &mut self,
-        commitment: [u8; PEDERSEN_COMMITMENT_LEN],
-    ) -> Result<()> {
-        if !self.commitments.contains(&commitment) {
-            self.commitments.push(commitment);
-            self.commitment_count += 1;
-            Ok(())
-        } else {
-            err!(KasbahErrors::CommitmentAlreadyExists)
-        }
+    pub const SPACE: usize = std::mem::size_of::<Self>();
+
+    /// Initializes pool `id` with the parameters of its registry
+    pub fn init(&mut self, id: u64, pool_registry: &PoolRegistry, bump: u8) -> Result<()> {
+        self.id = id;
+        self.mint = pool_registry.mint;
+        self.denomination = pool_registry.denomination;
+        self.vk_version = pool_registry.vk_version;
+        self.bump = bump;
+        self.merkle_tree.init(
+            pool_registry.merkle_tree_depth,
+            pool_registry.root_history_size,
+        )
     }
 
-    /// 20.000 CUs worst case
-    pub fn add_unique_nullifier(&mut self, nullifier: [u8; NULLIFIER_LEN]) {
-        if !self.nullifiers.contains(&nullifier) {
-            self.nullifiers.push(nullifier);
-            self.nullifier_count += 1;
-        }
+    /// Checks a deposit against the pool and appends its commitment, one Poseidon hash per
+    /// level of the tree. Returns the leaf index of the commitment
+    pub fn deposit(&mut self, commitment: [u8; COMMITMENT_LEN], amount: u64) -> Result<u32> {
+        // Leaves are hashed with Poseidon, they have to be canonical BN254 field elements
+        require!(
+            is_less_than_bn254_field_size_be(&commitment),
+            KasbahErrors::InvalidCommitment
+        );
+        require!(
+            amount == self.denomination,
+            KasbahErrors::InvalidDepositAmount
+        );
+        require!(!self.is_full(), KasbahErrors::CommitmentPoolFull);
+
+        self.merkle_tree.append(commitment)
     }
 
     pub fn is_full(&self) -> bool {
-        self.commitment_count >= MAX_COMMITMENTS_PER_POOL
+        self.merkle_tree.is_full()
     }
 }
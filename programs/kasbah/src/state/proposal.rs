@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    AddAdminInput, Config, CreatePoolInput, GenesisInput, KasbahErrors, RemoveAdminInput,
+    SetFeeInput, SetThresholdInput, WithdrawFeesInput, MAX_ADMINS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+/// Admin actions that need `Config::threshold` approvals, each carries the input of the
+/// instruction that executes it
+pub enum ProposalAction {
+    Genesis(GenesisInput),
+    CreatePool(CreatePoolInput),
+    SetFee(SetFeeInput),
+    WithdrawFees(WithdrawFeesInput),
+    AddAdmin(AddAdminInput),
+    RemoveAdmin(RemoveAdminInput),
+    SetThreshold(SetThresholdInput),
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub action: ProposalAction,
+    #[max_len(MAX_ADMINS)]
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub fn approve(&mut self, admin: Pubkey) -> Result<()> {
+        require!(!self.executed, KasbahErrors::ProposalAlreadyExecuted);
+        require!(
+            !self.approvals.contains(&admin),
+            KasbahErrors::AlreadyApproved
+        );
+        require!(
+            self.approvals.len() < MAX_ADMINS,
+            KasbahErrors::TooManyAdmins
+        );
+
+        self.approvals.push(admin);
+
+        Ok(())
+    }
+
+    /// Marks the proposal executed once `action` matches and enough current admins approved it
+    pub fn execute(&mut self, config: &Config, action: &ProposalAction) -> Result<()> {
+        require!(!self.executed, KasbahErrors::ProposalAlreadyExecuted);
+        require!(self.action == *action, KasbahErrors::ProposalMismatch);
+
+        // Approvals of admins removed since then no longer count
+        let approvals = self
+            .approvals
+            .iter()
+            .filter(|approval| config.is_admin(approval))
+            .count();
+        require!(
+            approvals >= config.threshold as usize,
+            KasbahErrors::ProposalNotApproved
+        );
+
+        self.executed = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(admins: Vec<Pubkey>, threshold: u8) -> Config {
+        Config {
+            admins,
+            deposits_paused: false,
+            redemptions_paused: false,
+            threshold,
+            proposal_count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_execute_after_threshold() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let config = config(vec![alice, bob], 2);
+        let action = ProposalAction::SetThreshold(SetThresholdInput { threshold: 1 });
+        let mut proposal = Proposal {
+            id: 0,
+            action: action.clone(),
+            approvals: vec![alice],
+            executed: false,
+            bump: 0,
+        };
+
+        assert!(proposal.execute(&config, &action).is_err());
+        assert!(proposal.approve(alice).is_err());
+
+        proposal.approve(bob).unwrap();
+        let other_action = ProposalAction::SetThreshold(SetThresholdInput { threshold: 2 });
+        assert!(proposal.execute(&config, &other_action).is_err());
+
+        proposal.execute(&config, &action).unwrap();
+        assert!(proposal.execute(&config, &action).is_err());
+    }
+}
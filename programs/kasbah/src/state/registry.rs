@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+/// One registry per mint and denomination, tracks the active commitment pool deposits are
+/// routed to
+pub struct PoolRegistry {
+    pub mint: Pubkey,
+    pub denomination: u64,
+    pub current_pool_id: u64,
+    /// Merkle tree depth of the pools created on rollover
+    pub merkle_tree_depth: u8,
+    /// Root history size of the pools created on rollover
+    pub root_history_size: u8,
+    /// Verifying key version of the pools created on rollover
+    pub vk_version: u32,
+    pub bump: u8,
+}
@@ -1,4 +1,9 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::MAX_FEE_BPS;
 
 #[account]
 #[derive(InitSpace)]
@@ -6,5 +11,37 @@ pub struct Treasury {
     /// Authorized pubkey to withdraw protocol fee
     pub authority: Pubkey,
     pub bump: u8,
+    /// Lamports charged on every deposit and redemption
     pub fixed_fee: u64,
+    /// Charged on top of `fixed_fee`, on the lamports moved by native SOL pools
+    pub fee_bps: u16,
+    /// Set by the authority, becomes the authority once it accepts the handover
+    pub pending_authority: Option<Pubkey>,
+}
+
+impl Treasury {
+    /// Fee in lamports for moving `lamports`, token pools pass 0 and only pay `fixed_fee`
+    pub fn fee(&self, lamports: u64) -> u64 {
+        let variable_fee = lamports as u128 * self.fee_bps as u128 / MAX_FEE_BPS as u128;
+        self.fixed_fee.saturating_add(variable_fee as u64)
+    }
+
+    /// Transfers the protocol fee from `payer` to the treasury, charged on deposits and redemptions
+    pub fn collect_fee<'info>(
+        treasury: &Account<'info, Self>,
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        lamports: u64,
+    ) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                system_program,
+                Transfer {
+                    from: payer,
+                    to: treasury.to_account_info(),
+                },
+            ),
+            treasury.fee(lamports),
+        )
+    }
 }
@@ -0,0 +1,205 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+use kasbah_groth16re::{Groth16Verifyingkey, PreparedVerifyingKey};
+
+use crate::{KasbahErrors, NR_PUBLIC_INPUTS, VK_IC_LEN};
+
+#[account]
+#[derive(InitSpace)]
+/// Groth16 verifying key of a `KasbahCommitmentCircuit` instance, one account per version.
+///
+/// Written by an admin, then frozen. Pools reference the version they were created under,
+/// so circuits can be upgraded without a program upgrade or affecting existing pools. The
+/// `CreatePool` proposal binds the `hash` of the key, not only its version.
+///
+/// Points are big-endian, G2 coordinates are ordered `(c1, c0)` as expected by the
+/// alt_bn128 syscalls, see the wasm `prepare_verifying_key` helper.
+pub struct VerifyingKeyAccount {
+    pub version: u32,
+    /// Depth of the merkle tree the circuit is instantiated with
+    pub merkle_tree_depth: u8,
+    /// No more writes once frozen
+    pub frozen: bool,
+    /// `KasbahPackedCommitmentCircuit`, the public inputs are hashed into a single one
+    pub packed_public_inputs: bool,
+    pub vk_alpha_g1: [u8; 64],
+    pub vk_beta_g2: [u8; 128],
+    pub vk_gamma_g2: [u8; 128],
+    pub vk_delta_g2: [u8; 128],
+    /// Number of `vk_ic` points written so far
+    pub ic_len: u8,
+    pub vk_ic: [[u8; 64]; VK_IC_LEN],
+    pub bump: u8,
+}
+
+impl VerifyingKeyAccount {
+    pub fn nr_public_inputs(&self) -> usize {
+        if self.packed_public_inputs {
+            1
+        } else {
+            NR_PUBLIC_INPUTS
+        }
+    }
+
+    pub fn append_ic(&mut self, points: &[[u8; 64]]) -> Result<()> {
+        require!(!self.frozen, KasbahErrors::VerifyingKeyFrozen);
+
+        let start = self.ic_len as usize;
+        let end = start + points.len();
+        require!(
+            end <= self.nr_public_inputs() + 1,
+            KasbahErrors::InvalidVerifyingKey
+        );
+
+        self.vk_ic[start..end].copy_from_slice(points);
+        self.ic_len = end as u8;
+
+        Ok(())
+    }
+
+    pub fn freeze(&mut self) -> Result<()> {
+        require!(!self.frozen, KasbahErrors::VerifyingKeyFrozen);
+        require!(
+            self.ic_len as usize == self.nr_public_inputs() + 1,
+            KasbahErrors::InvalidVerifyingKey
+        );
+
+        // Redemptions trust the points of frozen keys, see `verify_proof`
+        let verifying_key = self.to_groth16_verifyingkey();
+        let prepared = if self.packed_public_inputs {
+            PreparedVerifyingKey::<1>::new(&verifying_key).map(|_| ())
+        } else {
+            PreparedVerifyingKey::<NR_PUBLIC_INPUTS>::new(&verifying_key).map(|_| ())
+        };
+        prepared.map_err(|e| {
+            msg!("Invalid verifying key: {}", e);
+            KasbahErrors::InvalidVerifyingKey
+        })?;
+
+        self.frozen = true;
+
+        Ok(())
+    }
+
+    /// SHA-256 of the key points in account order, `vk_ic` up to the number of public inputs
+    pub fn hash(&self) -> [u8; 32] {
+        let mut points: Vec<&[u8]> = vec![
+            &self.vk_alpha_g1,
+            &self.vk_beta_g2,
+            &self.vk_gamma_g2,
+            &self.vk_delta_g2,
+        ];
+        points.extend(
+            self.vk_ic[..self.nr_public_inputs() + 1]
+                .iter()
+                .map(|point| point.as_slice()),
+        );
+
+        hashv(&points).to_bytes()
+    }
+
+    pub fn to_groth16_verifyingkey(&self) -> Groth16Verifyingkey<'_> {
+        Groth16Verifyingkey {
+            nr_pubinputs: self.nr_public_inputs(),
+            vk_alpha_g1: self.vk_alpha_g1,
+            vk_beta_g2: self.vk_beta_g2,
+            vk_gamme_g2: self.vk_gamma_g2,
+            vk_delta_g2: self.vk_delta_g2,
+            vk_ic: &self.vk_ic[..self.nr_public_inputs() + 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DISCRIMINATOR;
+
+    fn field_element(hex: &str) -> [u8; 32] {
+        let mut element = [0u8; 32];
+        for (byte, i) in element.iter_mut().zip((0..hex.len()).step_by(2)) {
+            *byte = u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        }
+        element
+    }
+
+    fn g1_generator() -> [u8; 64] {
+        let mut point = [0u8; 64];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    }
+
+    /// `(x.c1, x.c0, y.c1, y.c0)`
+    fn g2_generator() -> [u8; 128] {
+        [
+            field_element("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+            field_element("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+            field_element("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+            field_element("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+        ]
+        .concat()
+        .try_into()
+        .unwrap()
+    }
+
+    fn key_from_points(g1: [u8; 64], g2: [u8; 128]) -> VerifyingKeyAccount {
+        VerifyingKeyAccount {
+            version: 1,
+            merkle_tree_depth: 2,
+            frozen: false,
+            packed_public_inputs: false,
+            vk_alpha_g1: g1,
+            vk_beta_g2: g2,
+            vk_gamma_g2: g2,
+            vk_delta_g2: g2,
+            ic_len: 0,
+            vk_ic: [[0; 64]; VK_IC_LEN],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_init_space() {
+        let verifying_key = key_from_points(g1_generator(), g2_generator());
+        let mut data = Vec::new();
+        verifying_key.try_serialize(&mut data).unwrap();
+
+        assert_eq!(data.len(), DISCRIMINATOR + VerifyingKeyAccount::INIT_SPACE);
+    }
+
+    #[test]
+    fn test_freeze() {
+        let mut verifying_key = key_from_points(g1_generator(), g2_generator());
+        verifying_key.append_ic(&[g1_generator(); 4]).unwrap();
+        assert!(verifying_key.freeze().is_err());
+
+        verifying_key
+            .append_ic(&[g1_generator(); NR_PUBLIC_INPUTS - 3])
+            .unwrap();
+        verifying_key.freeze().unwrap();
+        assert!(verifying_key.frozen);
+
+        assert!(verifying_key.append_ic(&[g1_generator()]).is_err());
+        assert!(verifying_key.freeze().is_err());
+    }
+
+    #[test]
+    fn test_freeze_rejects_zero_key() {
+        // All identity points, any proof with `proof_a` at the identity would verify
+        let mut verifying_key = key_from_points([0; 64], [0; 128]);
+        verifying_key
+            .append_ic(&[[0; 64]; NR_PUBLIC_INPUTS + 1])
+            .unwrap();
+        assert!(verifying_key.freeze().is_err());
+        assert!(!verifying_key.frozen);
+
+        // A single identity point is enough
+        let mut verifying_key = key_from_points(g1_generator(), g2_generator());
+        verifying_key.vk_gamma_g2 = [0; 128];
+        verifying_key
+            .append_ic(&[g1_generator(); NR_PUBLIC_INPUTS + 1])
+            .unwrap();
+        assert!(verifying_key.freeze().is_err());
+        assert!(!verifying_key.frozen);
+    }
+}
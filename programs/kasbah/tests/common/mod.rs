@@ -0,0 +1,924 @@
+//! Instruction-level harness, runs the program natively in `solana-program-test` with a
+//! stand-in circuit that has the public inputs of `KasbahCommitmentCircuit`
+#![allow(dead_code)]
+
+use std::ops::Neg;
+
+use anchor_lang::{
+    error::{ErrorCode, ERROR_CODE_OFFSET},
+    prelude::{AccountInfo, Pubkey, Rent},
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction},
+    system_program, AccountDeserialize, AccountSerialize, InstructionData, Space, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+            StateWithExtensions,
+        },
+        instruction::{initialize_mint2, mint_to},
+    },
+};
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Groth16, ProvingKey};
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+};
+use ark_serialize::CanonicalSerialize;
+use ark_snark::SNARK;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use kasbah::{
+    pack_public_inputs, pubkey_to_field_elements, u64_to_field_element, CommitmentPool, Config,
+    CreatePoolInput, DepositInput, GenesisInput, InitVerifyingKeyInput, KasbahErrors, PauseInput,
+    ProposalAction, RedeemInput, VerifyingKeyAccount, WithdrawFeesInput,
+    COMMITMENT_POOL_GENESIS_ID, COMMITMENT_POOL_SEED, CONFIG_SEED, DISCRIMINATOR,
+    GROTH16_PROOF_LEN, NATIVE_SOL_MINT, NR_PUBLIC_INPUTS, NULLIFIER_SEED, POOL_REGISTRY_SEED,
+    PROPOSAL_SEED, TREASURY_SEED, VERIFYING_KEY_SEED,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+/// Depth of `ksb_2.circom`, 4 deposits fill a pool
+pub const MERKLE_TREE_DEPTH: u8 = 2;
+pub const ROOT_HISTORY_SIZE: u8 = 10;
+pub const DENOMINATION: u64 = 1_000_000_000;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // `entry` ties the slice and the accounts to the same lifetime
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    kasbah::entry(program_id, accounts, data)
+}
+
+/// Binds `N` public inputs without constraining them any further, the program only checks the
+/// proof against the public inputs it derives from the redemption
+#[derive(Clone)]
+struct PublicInputsCircuit<const N: usize> {
+    public_inputs: [Fr; N],
+}
+
+impl<const N: usize> ConstraintSynthesizer<Fr> for PublicInputsCircuit<N> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        for public_input in self.public_inputs {
+            let input = cs.new_input_variable(|| Ok(public_input))?;
+            cs.enforce_constraint(lc!() + input, lc!() + Variable::One, lc!() + input)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Groth16 setup of `PublicInputsCircuit`, 8 public inputs or a single packed one
+pub struct Prover {
+    proving_key: ProvingKey<Bn254>,
+    pub packed_public_inputs: bool,
+}
+
+impl Prover {
+    pub fn new(packed_public_inputs: bool) -> Self {
+        let mut rng = StdRng::seed_from_u64(packed_public_inputs as u64);
+        let proving_key = if packed_public_inputs {
+            Groth16::<Bn254>::circuit_specific_setup(
+                PublicInputsCircuit {
+                    public_inputs: [Fr::from(0u64); 1],
+                },
+                &mut rng,
+            )
+        } else {
+            Groth16::<Bn254>::circuit_specific_setup(
+                PublicInputsCircuit {
+                    public_inputs: [Fr::from(0u64); NR_PUBLIC_INPUTS],
+                },
+                &mut rng,
+            )
+        }
+        .unwrap()
+        .0;
+
+        Self {
+            proving_key,
+            packed_public_inputs,
+        }
+    }
+
+    pub fn init_verifying_key_input(&self, version: u32) -> InitVerifyingKeyInput {
+        let vk = &self.proving_key.vk;
+        InitVerifyingKeyInput {
+            version,
+            merkle_tree_depth: MERKLE_TREE_DEPTH,
+            packed_public_inputs: self.packed_public_inputs,
+            vk_alpha_g1: g1_to_bytes(&vk.alpha_g1),
+            vk_beta_g2: g2_to_bytes(&vk.beta_g2),
+            vk_gamma_g2: g2_to_bytes(&vk.gamma_g2),
+            vk_delta_g2: g2_to_bytes(&vk.delta_g2),
+        }
+    }
+
+    pub fn vk_ic(&self) -> Vec<[u8; 64]> {
+        self.proving_key
+            .vk
+            .gamma_abc_g1
+            .iter()
+            .map(g1_to_bytes)
+            .collect()
+    }
+
+    /// Proof in the `prepare_proofs` encoding of the wasm crate
+    pub fn prove(&self, public_inputs: &[[u8; 32]; NR_PUBLIC_INPUTS]) -> [u8; GROTH16_PROOF_LEN] {
+        let mut rng = StdRng::seed_from_u64(42);
+        let to_fr = |input: &[u8; 32]| Fr::from_be_bytes_mod_order(input);
+        let proof = if self.packed_public_inputs {
+            Groth16::<Bn254>::prove(
+                &self.proving_key,
+                PublicInputsCircuit {
+                    public_inputs: [to_fr(&pack_public_inputs(public_inputs))],
+                },
+                &mut rng,
+            )
+        } else {
+            Groth16::<Bn254>::prove(
+                &self.proving_key,
+                PublicInputsCircuit {
+                    public_inputs: public_inputs.map(|input| to_fr(&input)),
+                },
+                &mut rng,
+            )
+        }
+        .unwrap();
+
+        [
+            compress::<_, 32>(&proof.a.neg()).to_vec(),
+            compress::<_, 64>(&proof.b).to_vec(),
+            compress::<_, 32>(&proof.c).to_vec(),
+        ]
+        .concat()
+        .try_into()
+        .unwrap()
+    }
+}
+
+fn g1_to_bytes(point: &G1Affine) -> [u8; 64] {
+    [
+        point.x.into_bigint().to_bytes_be(),
+        point.y.into_bigint().to_bytes_be(),
+    ]
+    .concat()
+    .try_into()
+    .unwrap()
+}
+
+fn g2_to_bytes(point: &G2Affine) -> [u8; 128] {
+    [
+        point.x.c1.into_bigint().to_bytes_be(),
+        point.x.c0.into_bigint().to_bytes_be(),
+        point.y.c1.into_bigint().to_bytes_be(),
+        point.y.c0.into_bigint().to_bytes_be(),
+    ]
+    .concat()
+    .try_into()
+    .unwrap()
+}
+
+/// Arkworks' compressed encoding reversed to big-endian, the layout of the decompression
+/// syscalls
+fn compress<P: CanonicalSerialize, const N: usize>(point: &P) -> [u8; N] {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes.reverse();
+    bytes.try_into().unwrap()
+}
+
+/// Field element with `value` in its last byte, a valid commitment or nullifier hash
+pub fn field_element(value: u8) -> [u8; 32] {
+    let mut element = [0u8; 32];
+    element[31] = value;
+    element
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED], &kasbah::ID).0
+}
+
+pub fn treasury_pda() -> Pubkey {
+    Pubkey::find_program_address(&[TREASURY_SEED], &kasbah::ID).0
+}
+
+pub fn proposal_pda(id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[PROPOSAL_SEED, id.to_le_bytes().as_ref()], &kasbah::ID).0
+}
+
+pub fn verifying_key_pda(version: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VERIFYING_KEY_SEED, version.to_le_bytes().as_ref()],
+        &kasbah::ID,
+    )
+    .0
+}
+
+pub fn pool_registry_pda(mint: &Pubkey, denomination: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            POOL_REGISTRY_SEED,
+            mint.as_ref(),
+            denomination.to_le_bytes().as_ref(),
+        ],
+        &kasbah::ID,
+    )
+    .0
+}
+
+pub fn commitment_pool_pda(mint: &Pubkey, denomination: u64, pool_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            COMMITMENT_POOL_SEED,
+            mint.as_ref(),
+            denomination.to_le_bytes().as_ref(),
+            pool_id.to_le_bytes().as_ref(),
+        ],
+        &kasbah::ID,
+    )
+    .0
+}
+
+pub fn nullifier_pda(nullifier_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[NULLIFIER_SEED, nullifier_hash.as_ref()], &kasbah::ID).0
+}
+
+pub fn assert_kasbah_error<T: std::fmt::Debug>(
+    result: Result<T, BanksClientError>,
+    error: KasbahErrors,
+) {
+    assert_custom_error(result, error as u32 + ERROR_CODE_OFFSET);
+}
+
+pub fn assert_anchor_error<T: std::fmt::Debug>(
+    result: Result<T, BanksClientError>,
+    error: ErrorCode,
+) {
+    assert_custom_error(result, error.into());
+}
+
+fn assert_custom_error<T: std::fmt::Debug>(result: Result<T, BanksClientError>, expected: u32) {
+    match result.unwrap_err() {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected),
+        other => panic!("expected custom error {expected}, got {other:?}"),
+    }
+}
+
+/// Redemption of a deposit, public inputs are derived the same way as the program does
+pub struct Redemption {
+    pub mint: Pubkey,
+    pub denomination: u64,
+    pub commitment_pool_id: u64,
+    pub root: [u8; 32],
+    pub nullifier_hash: [u8; 32],
+    pub bob: Pubkey,
+    pub relayer_fee: u64,
+    pub refund: u64,
+}
+
+impl Redemption {
+    pub fn public_inputs(&self, relayer: &Pubkey) -> [[u8; 32]; NR_PUBLIC_INPUTS] {
+        let [recipient_hi, recipient_lo] = pubkey_to_field_elements(&self.bob);
+        let [relayer_hi, relayer_lo] = pubkey_to_field_elements(relayer);
+        [
+            self.root,
+            self.nullifier_hash,
+            recipient_hi,
+            recipient_lo,
+            relayer_hi,
+            relayer_lo,
+            u64_to_field_element(self.relayer_fee),
+            u64_to_field_element(self.refund),
+        ]
+    }
+
+    pub fn input(&self, proof: [u8; GROTH16_PROOF_LEN]) -> RedeemInput {
+        RedeemInput {
+            denomination: self.denomination,
+            commitment_pool_id: self.commitment_pool_id,
+            proof,
+            root: self.root,
+            nullifier_hash: self.nullifier_hash,
+            relayer_fee: self.relayer_fee,
+            refund: self.refund,
+        }
+    }
+}
+
+/// Program with a single admin approving its own proposals and a treasury, the config is
+/// written directly since `initialize_config` needs an upgradeable program
+pub struct Kasbah {
+    pub context: ProgramTestContext,
+    pub treasury_authority: Keypair,
+    proposal_count: u64,
+}
+
+impl Kasbah {
+    pub async fn new() -> Self {
+        let program_test = ProgramTest::new("kasbah", kasbah::ID, processor!(process_instruction));
+        let context = program_test.start_with_context().await;
+
+        let mut kasbah = Self {
+            context,
+            treasury_authority: Keypair::new(),
+            proposal_count: 0,
+        };
+        let config = Config {
+            admins: vec![kasbah.admin()],
+            deposits_paused: false,
+            redemptions_paused: false,
+            threshold: 1,
+            proposal_count: 0,
+            bump: Pubkey::find_program_address(&[CONFIG_SEED], &kasbah::ID).1,
+        };
+        kasbah.set_config(&config);
+        let treasury_authority = kasbah.treasury_authority.pubkey();
+        kasbah.airdrop(&treasury_authority, 1_000_000_000);
+        kasbah
+            .admin_action(ProposalAction::Genesis(GenesisInput { treasury_authority }))
+            .await
+            .unwrap();
+
+        kasbah
+    }
+
+    fn config_account(config: &Config) -> Account {
+        let mut data = Vec::with_capacity(DISCRIMINATOR + Config::INIT_SPACE);
+        config.try_serialize(&mut data).unwrap();
+        data.resize(DISCRIMINATOR + Config::INIT_SPACE, 0);
+
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: kasbah::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    pub fn admin(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Sends `instructions` paid by the admin, returns the logs
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<String>, BanksClientError> {
+        // Retried transactions would otherwise be rejected as already processed
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let result = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await?;
+        result.result?;
+
+        Ok(result
+            .metadata
+            .map(|metadata| metadata.log_messages)
+            .unwrap_or_default())
+    }
+
+    pub fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let account = AccountSharedData::new(lamports, 0, &system_program::ID);
+        self.context.set_account(to, &account);
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.account(address)
+            .await
+            .map_or(0, |account| account.lamports)
+    }
+
+    pub async fn anchor_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.account(address).await.unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn config(&mut self) -> Config {
+        self.anchor_account(&config_pda()).await
+    }
+
+    pub fn set_config(&mut self, config: &Config) {
+        let account = Self::config_account(config);
+        self.context.set_account(&config_pda(), &account.into());
+    }
+
+    /// Proposes `action` and executes it with the matching instruction, the admin's own
+    /// approval meets the threshold of 1
+    pub async fn admin_action(
+        &mut self,
+        action: ProposalAction,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let (proposal, propose) = self.propose_instruction(&action);
+        let execute = match action {
+            ProposalAction::Genesis(input) => Instruction {
+                program_id: kasbah::ID,
+                accounts: kasbah::accounts::GenesisAccounts {
+                    admin: self.admin(),
+                    config: config_pda(),
+                    proposal,
+                    system_program: system_program::ID,
+                    treasury: treasury_pda(),
+                }
+                .to_account_metas(None),
+                data: kasbah::instruction::Genesis { input }.data(),
+            },
+            ProposalAction::CreatePool(input) => {
+                let mint = (input.mint != NATIVE_SOL_MINT).then_some(input.mint);
+                Instruction {
+                    program_id: kasbah::ID,
+                    accounts: kasbah::accounts::CreatePoolAccounts {
+                        admin: self.admin(),
+                        config: config_pda(),
+                        proposal,
+                        system_program: system_program::ID,
+                        mint,
+                        verifying_key: verifying_key_pda(input.vk_version),
+                        pool_registry: pool_registry_pda(&input.mint, input.denomination),
+                        commitment_pool: commitment_pool_pda(
+                            &input.mint,
+                            input.denomination,
+                            COMMITMENT_POOL_GENESIS_ID,
+                        ),
+                    }
+                    .to_account_metas(None),
+                    data: kasbah::instruction::CreatePool { input }.data(),
+                }
+            }
+            ProposalAction::SetFee(input) => Instruction {
+                program_id: kasbah::ID,
+                accounts: kasbah::accounts::SetFeeAccounts {
+                    admin: self.admin(),
+                    config: config_pda(),
+                    proposal,
+                    treasury: treasury_pda(),
+                }
+                .to_account_metas(None),
+                data: kasbah::instruction::SetFee { input }.data(),
+            },
+            action => unimplemented!("{:?}", std::mem::discriminant(&action)),
+        };
+
+        self.process(&[propose], &[]).await?;
+        self.process(&[execute], &[]).await
+    }
+
+    /// Next proposal, approved by the admin who opens it
+    fn propose_instruction(&mut self, action: &ProposalAction) -> (Pubkey, Instruction) {
+        let proposal = proposal_pda(self.proposal_count);
+        self.proposal_count += 1;
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::ProposeAccounts {
+                admin: self.admin(),
+                config: config_pda(),
+                proposal,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::Propose {
+                input: kasbah::ProposeInput {
+                    action: action.clone(),
+                },
+            }
+            .data(),
+        };
+
+        (proposal, instruction)
+    }
+
+    /// Proposes and executes a withdrawal of `amount` lamports of fees paid out to `receiver`
+    pub async fn withdraw_fees(
+        &mut self,
+        amount: u64,
+        receiver: &Pubkey,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let input = WithdrawFeesInput { amount };
+        let (proposal, propose) =
+            self.propose_instruction(&ProposalAction::WithdrawFees(input.clone()));
+        let withdraw_fees = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::WithdrawFeesAccounts {
+                admin: self.admin(),
+                config: config_pda(),
+                proposal,
+                treasury: treasury_pda(),
+                receiver: *receiver,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::WithdrawFees { input }.data(),
+        };
+
+        self.process(&[propose], &[]).await?;
+        self.process(&[withdraw_fees], &[]).await
+    }
+
+    /// `pause`, or `unpause` when `paused` is false, signed by `admin`
+    pub async fn set_paused(
+        &mut self,
+        admin: &Keypair,
+        paused: bool,
+        deposits: bool,
+        redemptions: bool,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let input = PauseInput {
+            deposits,
+            redemptions,
+        };
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::PauseAccounts {
+                admin: admin.pubkey(),
+                config: config_pda(),
+            }
+            .to_account_metas(None),
+            data: if paused {
+                kasbah::instruction::Pause { input }.data()
+            } else {
+                kasbah::instruction::Unpause { input }.data()
+            },
+        };
+
+        self.process(&[instruction], &[admin]).await
+    }
+
+    /// Uploads the verifying key of `prover` under `version`, frozen when `freeze` is set
+    pub async fn upload_verifying_key(&mut self, prover: &Prover, version: u32, freeze: bool) {
+        let verifying_key = verifying_key_pda(version);
+        let mut instructions = vec![Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::InitVerifyingKeyAccounts {
+                admin: self.admin(),
+                config: config_pda(),
+                verifying_key,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::InitVerifyingKey {
+                input: prover.init_verifying_key_input(version),
+            }
+            .data(),
+        }];
+        instructions.push(Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::AppendVerifyingKeyIcAccounts {
+                admin: self.admin(),
+                config: config_pda(),
+                verifying_key,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::AppendVerifyingKeyIc {
+                input: kasbah::AppendVerifyingKeyIcInput {
+                    points: prover.vk_ic(),
+                },
+            }
+            .data(),
+        });
+        if freeze {
+            instructions.push(Instruction {
+                program_id: kasbah::ID,
+                accounts: kasbah::accounts::FreezeVerifyingKeyAccounts {
+                    admin: self.admin(),
+                    config: config_pda(),
+                    verifying_key,
+                }
+                .to_account_metas(None),
+                data: kasbah::instruction::FreezeVerifyingKey {}.data(),
+            });
+        }
+
+        for instruction in instructions {
+            self.process(&[instruction], &[]).await.unwrap();
+        }
+    }
+
+    /// Creates the pools of `mint` and `denomination` under the frozen key `vk_version`
+    pub async fn create_pool(
+        &mut self,
+        mint: Pubkey,
+        denomination: u64,
+        vk_version: u32,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let verifying_key: VerifyingKeyAccount =
+            self.anchor_account(&verifying_key_pda(vk_version)).await;
+        self.admin_action(ProposalAction::CreatePool(CreatePoolInput {
+            mint,
+            denomination,
+            merkle_tree_depth: MERKLE_TREE_DEPTH,
+            root_history_size: ROOT_HISTORY_SIZE,
+            vk_version,
+            vk_hash: verifying_key.hash(),
+        }))
+        .await
+    }
+
+    /// Zero-copy accounts aren't aligned in the returned data
+    pub async fn commitment_pool(&mut self, address: &Pubkey) -> CommitmentPool {
+        let account = self.account(address).await.unwrap();
+        bytemuck::pod_read_unaligned(&account.data[DISCRIMINATOR..])
+    }
+
+    /// Native SOL deposit of `commitment` by `alice`, returns the new root of the pool
+    pub async fn deposit(
+        &mut self,
+        alice: &Keypair,
+        commitment: [u8; 32],
+        amount: u64,
+        pool_id: u64,
+    ) -> Result<[u8; 32], BanksClientError> {
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::DepositAccounts {
+                alice: alice.pubkey(),
+                config: config_pda(),
+                treasury: treasury_pda(),
+                pool_registry: pool_registry_pda(&NATIVE_SOL_MINT, amount),
+                commitment_pool: commitment_pool_pda(&NATIVE_SOL_MINT, amount, pool_id),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::Deposit {
+                input: DepositInput { commitment, amount },
+            }
+            .data(),
+        };
+
+        self.process(&[instruction], &[alice]).await?;
+        let commitment_pool = commitment_pool_pda(&NATIVE_SOL_MINT, amount, pool_id);
+        Ok(self
+            .commitment_pool(&commitment_pool)
+            .await
+            .merkle_tree
+            .root())
+    }
+
+    /// Native SOL redemption submitted by `relayer`, verifying against `vk_version`
+    pub async fn redeem(
+        &mut self,
+        relayer: &Keypair,
+        redemption: &Redemption,
+        proof: [u8; GROTH16_PROOF_LEN],
+        vk_version: u32,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::RedeemAccounts {
+                relayer: relayer.pubkey(),
+                bob: redemption.bob,
+                config: config_pda(),
+                commitment_pool: commitment_pool_pda(
+                    &NATIVE_SOL_MINT,
+                    redemption.denomination,
+                    redemption.commitment_pool_id,
+                ),
+                verifying_key: verifying_key_pda(vk_version),
+                nullifier: nullifier_pda(&redemption.nullifier_hash),
+                treasury: treasury_pda(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::Redeem {
+                input: redemption.input(proof),
+            }
+            .data(),
+        };
+
+        self.process(&[instruction], &[relayer]).await
+    }
+
+    /// Mint of `token_program` with the admin as authority, withholding `transfer_fee_bps` of
+    /// every transfer when set
+    pub async fn create_mint(
+        &mut self,
+        token_program: &Pubkey,
+        transfer_fee_bps: Option<u16>,
+    ) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let extensions: &[ExtensionType] = match transfer_fee_bps {
+            Some(_) => &[ExtensionType::TransferFeeConfig],
+            None => &[],
+        };
+        let len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+                .unwrap();
+        let account =
+            AccountSharedData::new(Rent::default().minimum_balance(len), len, token_program);
+        self.context.set_account(&mint, &account);
+
+        let mut instructions = vec![];
+        if let Some(transfer_fee_bps) = transfer_fee_bps {
+            instructions.push(
+                initialize_transfer_fee_config(
+                    token_program,
+                    &mint,
+                    None,
+                    None,
+                    transfer_fee_bps,
+                    u64::MAX,
+                )
+                .unwrap(),
+            );
+        }
+        instructions.push(initialize_mint2(token_program, &mint, &self.admin(), None, 6).unwrap());
+        self.process(&instructions, &[]).await.unwrap();
+
+        mint
+    }
+
+    /// Mints `amount` to the associated token account of `owner`, created if needed
+    pub async fn mint_to(
+        &mut self,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+    ) -> Pubkey {
+        let token_account = associated_token_address(owner, mint, token_program);
+        let instructions = [
+            create_associated_token_account_idempotent(&self.admin(), owner, mint, token_program),
+            mint_to(
+                token_program,
+                mint,
+                &token_account,
+                &self.admin(),
+                &[],
+                amount,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[]).await.unwrap();
+
+        token_account
+    }
+
+    /// 0 for token accounts that don't exist yet
+    pub async fn token_balance(&mut self, token_account: &Pubkey) -> u64 {
+        let Some(account) = self.account(token_account).await else {
+            return 0;
+        };
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+            .unwrap()
+            .base
+            .amount
+    }
+
+    /// Deposit of `amount` tokens by `alice` out of her associated token account, returns the
+    /// new root of the pool
+    pub async fn deposit_token(
+        &mut self,
+        alice: &Keypair,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        commitment: [u8; 32],
+        amount: u64,
+        pool_id: u64,
+    ) -> Result<[u8; 32], BanksClientError> {
+        let commitment_pool = commitment_pool_pda(mint, amount, pool_id);
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::DepositTokenAccounts {
+                alice: alice.pubkey(),
+                config: config_pda(),
+                mint: *mint,
+                alice_token_account: associated_token_address(&alice.pubkey(), mint, token_program),
+                treasury: treasury_pda(),
+                pool_registry: pool_registry_pda(mint, amount),
+                commitment_pool,
+                vault: associated_token_address(&commitment_pool, mint, token_program),
+                token_program: *token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::DepositToken {
+                input: DepositInput { commitment, amount },
+            }
+            .data(),
+        };
+
+        self.process(&[instruction], &[alice]).await?;
+        Ok(self
+            .commitment_pool(&commitment_pool)
+            .await
+            .merkle_tree
+            .root())
+    }
+
+    /// Token redemption of `redemption.mint` submitted by `relayer`
+    pub async fn redeem_token(
+        &mut self,
+        relayer: &Keypair,
+        redemption: &Redemption,
+        proof: [u8; GROTH16_PROOF_LEN],
+        vk_version: u32,
+        token_program: &Pubkey,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let mint = &redemption.mint;
+        let commitment_pool =
+            commitment_pool_pda(mint, redemption.denomination, redemption.commitment_pool_id);
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::RedeemTokenAccounts {
+                relayer: relayer.pubkey(),
+                bob: redemption.bob,
+                config: config_pda(),
+                mint: *mint,
+                bob_token_account: associated_token_address(&redemption.bob, mint, token_program),
+                relayer_token_account: associated_token_address(
+                    &relayer.pubkey(),
+                    mint,
+                    token_program,
+                ),
+                commitment_pool,
+                verifying_key: verifying_key_pda(vk_version),
+                vault: associated_token_address(&commitment_pool, mint, token_program),
+                nullifier: nullifier_pda(&redemption.nullifier_hash),
+                treasury: treasury_pda(),
+                token_program: *token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::RedeemToken {
+                input: redemption.input(proof),
+            }
+            .data(),
+        };
+
+        self.process(&[instruction], &[relayer]).await
+    }
+
+    /// Rolls the full pool `pool_id` of `mint` and `denomination` over to `pool_id + 1`
+    pub async fn rollover(
+        &mut self,
+        mint: &Pubkey,
+        denomination: u64,
+        pool_id: u64,
+    ) -> Result<Vec<String>, BanksClientError> {
+        let instruction = Instruction {
+            program_id: kasbah::ID,
+            accounts: kasbah::accounts::RolloverAccounts {
+                payer: self.admin(),
+                config: config_pda(),
+                pool_registry: pool_registry_pda(mint, denomination),
+                commitment_pool: commitment_pool_pda(mint, denomination, pool_id),
+                next_commitment_pool: commitment_pool_pda(mint, denomination, pool_id + 1),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: kasbah::instruction::Rollover {}.data(),
+        };
+
+        self.process(&[instruction], &[]).await
+    }
+
+    /// Keypair holding `lamports`
+    pub fn funded_keypair(&mut self, lamports: u64) -> Keypair {
+        let keypair = Keypair::new();
+        self.airdrop(&keypair.pubkey(), lamports);
+        keypair
+    }
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
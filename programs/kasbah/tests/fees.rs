@@ -0,0 +1,149 @@
+mod common;
+
+use anchor_lang::{prelude::Rent, Space};
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use kasbah::{
+    KasbahErrors, ProposalAction, SetFeeInput, Treasury, DISCRIMINATOR, MAX_FEE_BPS,
+    NATIVE_SOL_MINT,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const FIXED_FEE: u64 = 5_000;
+const FEE_BPS: u16 = 100;
+
+async fn set_fee(kasbah: &mut Kasbah, fixed_fee: u64, fee_bps: u16) {
+    kasbah
+        .admin_action(ProposalAction::SetFee(SetFeeInput { fixed_fee, fee_bps }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_fees() {
+    let prover = Prover::new(false);
+    let mut kasbah = Kasbah::new().await;
+    kasbah.upload_verifying_key(&prover, 1, true).await;
+    kasbah
+        .create_pool(NATIVE_SOL_MINT, DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    assert_kasbah_error(
+        kasbah
+            .admin_action(ProposalAction::SetFee(SetFeeInput {
+                fixed_fee: FIXED_FEE,
+                fee_bps: MAX_FEE_BPS + 1,
+            }))
+            .await,
+        KasbahErrors::InvalidFee,
+    );
+    set_fee(&mut kasbah, FIXED_FEE, FEE_BPS).await;
+    let fee = FIXED_FEE + DENOMINATION * FEE_BPS as u64 / MAX_FEE_BPS as u64;
+
+    // Native SOL deposits pay the fixed fee plus the bps on the denomination
+    let alice = kasbah.funded_keypair(2 * DENOMINATION);
+    let treasury_balance = kasbah.lamports(&treasury_pda()).await;
+    let root = kasbah
+        .deposit(&alice, field_element(1), DENOMINATION, 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        kasbah.lamports(&treasury_pda()).await,
+        treasury_balance + fee
+    );
+    assert_eq!(kasbah.lamports(&alice.pubkey()).await, DENOMINATION - fee);
+
+    // And so do redemptions, out of the relayer
+    let redemption = Redemption {
+        mint: NATIVE_SOL_MINT,
+        denomination: DENOMINATION,
+        commitment_pool_id: 1,
+        root,
+        nullifier_hash: field_element(2),
+        bob: Keypair::new().pubkey(),
+        relayer_fee: 0,
+        refund: 0,
+    };
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+    kasbah
+        .redeem(&relayer, &redemption, proof, 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        kasbah.lamports(&treasury_pda()).await,
+        treasury_balance + 2 * fee
+    );
+    assert_eq!(kasbah.lamports(&redemption.bob).await, DENOMINATION);
+
+    // Token pools only pay the fixed fee
+    let token_program = spl_token_2022::ID;
+    let mint = kasbah.create_mint(&token_program, None).await;
+    kasbah.create_pool(mint, DENOMINATION, 1).await.unwrap();
+    kasbah
+        .mint_to(&mint, &token_program, &alice.pubkey(), DENOMINATION)
+        .await;
+    kasbah
+        .deposit_token(
+            &alice,
+            &mint,
+            &token_program,
+            field_element(3),
+            DENOMINATION,
+            1,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        kasbah.lamports(&treasury_pda()).await,
+        treasury_balance + 2 * fee + FIXED_FEE
+    );
+}
+
+#[tokio::test]
+async fn test_withdraw_fees() {
+    let prover = Prover::new(false);
+    let mut kasbah = Kasbah::new().await;
+    kasbah.upload_verifying_key(&prover, 1, true).await;
+    kasbah
+        .create_pool(NATIVE_SOL_MINT, DENOMINATION, 1)
+        .await
+        .unwrap();
+    set_fee(&mut kasbah, FIXED_FEE, FEE_BPS).await;
+    let alice = kasbah.funded_keypair(2 * DENOMINATION);
+    kasbah
+        .deposit(&alice, field_element(1), DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    let rent_exempt_balance = Rent::default().minimum_balance(DISCRIMINATOR + Treasury::INIT_SPACE);
+    let withdrawable = kasbah.lamports(&treasury_pda()).await - rent_exempt_balance;
+    let authority = kasbah.treasury_authority.pubkey();
+    let authority_balance = kasbah.lamports(&authority).await;
+
+    assert_kasbah_error(
+        kasbah.withdraw_fees(withdrawable + 1, &authority).await,
+        KasbahErrors::InsufficientTreasuryFunds,
+    );
+    assert_kasbah_error(
+        kasbah
+            .withdraw_fees(withdrawable, &Pubkey::new_unique())
+            .await,
+        KasbahErrors::InvalidTreasuryAuthority,
+    );
+
+    kasbah
+        .withdraw_fees(withdrawable, &authority)
+        .await
+        .unwrap();
+    assert_eq!(kasbah.lamports(&treasury_pda()).await, rent_exempt_balance);
+    assert_eq!(
+        kasbah.lamports(&authority).await,
+        authority_balance + withdrawable
+    );
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::*;
+use kasbah::{KasbahErrors, NATIVE_SOL_MINT};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Pool of `DENOMINATION` lamports under key version 1, returns the prover and alice
+async fn setup() -> (Kasbah, Prover, Keypair) {
+    let prover = Prover::new(false);
+    let mut kasbah = Kasbah::new().await;
+    kasbah.upload_verifying_key(&prover, 1, true).await;
+    kasbah
+        .create_pool(NATIVE_SOL_MINT, DENOMINATION, 1)
+        .await
+        .unwrap();
+    let alice = kasbah.funded_keypair(10 * DENOMINATION);
+
+    (kasbah, prover, alice)
+}
+
+fn redemption(root: [u8; 32], nullifier_hash: [u8; 32]) -> Redemption {
+    Redemption {
+        mint: NATIVE_SOL_MINT,
+        denomination: DENOMINATION,
+        commitment_pool_id: 1,
+        root,
+        nullifier_hash,
+        bob: Keypair::new().pubkey(),
+        relayer_fee: 0,
+        refund: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_pause_deposits() {
+    let (mut kasbah, prover, alice) = setup().await;
+    let root = kasbah
+        .deposit(&alice, field_element(1), DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    let admin = kasbah.context.payer.insecure_clone();
+    kasbah.set_paused(&admin, true, true, false).await.unwrap();
+    assert_kasbah_error(
+        kasbah
+            .deposit(&alice, field_element(2), DENOMINATION, 1)
+            .await,
+        KasbahErrors::ProtocolPaused,
+    );
+
+    // Redemptions are toggled separately
+    let redemption = redemption(root, field_element(3));
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+    kasbah
+        .redeem(&relayer, &redemption, proof, 1)
+        .await
+        .unwrap();
+
+    kasbah.set_paused(&admin, false, true, false).await.unwrap();
+    kasbah
+        .deposit(&alice, field_element(2), DENOMINATION, 1)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pause_redemptions() {
+    let (mut kasbah, prover, alice) = setup().await;
+    let root = kasbah
+        .deposit(&alice, field_element(1), DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    let admin = kasbah.context.payer.insecure_clone();
+    kasbah.set_paused(&admin, true, false, true).await.unwrap();
+    let redemption = redemption(root, field_element(3));
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &redemption, proof, 1).await,
+        KasbahErrors::ProtocolPaused,
+    );
+
+    // Deposits are toggled separately
+    kasbah
+        .deposit(&alice, field_element(2), DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    kasbah.set_paused(&admin, false, false, true).await.unwrap();
+    kasbah
+        .redeem(&relayer, &redemption, proof, 1)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pause_rollover() {
+    let (mut kasbah, _, alice) = setup().await;
+    for i in 0..4 {
+        kasbah
+            .deposit(&alice, field_element(i + 1), DENOMINATION, 1)
+            .await
+            .unwrap();
+    }
+
+    let admin = kasbah.context.payer.insecure_clone();
+    kasbah.set_paused(&admin, true, true, false).await.unwrap();
+    assert_kasbah_error(
+        kasbah.rollover(&NATIVE_SOL_MINT, DENOMINATION, 1).await,
+        KasbahErrors::ProtocolPaused,
+    );
+
+    kasbah.set_paused(&admin, false, true, false).await.unwrap();
+    kasbah
+        .rollover(&NATIVE_SOL_MINT, DENOMINATION, 1)
+        .await
+        .unwrap();
+    kasbah
+        .deposit(&alice, field_element(5), DENOMINATION, 2)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pause_is_admin_only() {
+    let (mut kasbah, _, alice) = setup().await;
+
+    assert_kasbah_error(
+        kasbah.set_paused(&alice, true, true, true).await,
+        KasbahErrors::InvalidAdmin,
+    );
+    let config = kasbah.config().await;
+    assert!(!config.deposits_paused && !config.redemptions_paused);
+}
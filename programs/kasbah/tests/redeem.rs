@@ -0,0 +1,112 @@
+mod common;
+
+use common::*;
+use kasbah::{KasbahErrors, NATIVE_SOL_MINT};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Pool of `DENOMINATION` lamports under key version 1 holding one deposit, returns the
+/// redemption of that deposit to a fresh bob
+async fn setup(prover: &Prover) -> (Kasbah, Redemption) {
+    let mut kasbah = Kasbah::new().await;
+    kasbah.upload_verifying_key(prover, 1, true).await;
+    kasbah
+        .create_pool(NATIVE_SOL_MINT, DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    let alice = kasbah.funded_keypair(2 * DENOMINATION);
+    let root = kasbah
+        .deposit(&alice, field_element(1), DENOMINATION, 1)
+        .await
+        .unwrap();
+
+    let redemption = Redemption {
+        mint: NATIVE_SOL_MINT,
+        denomination: DENOMINATION,
+        commitment_pool_id: 1,
+        root,
+        nullifier_hash: field_element(2),
+        bob: Keypair::new().pubkey(),
+        relayer_fee: 0,
+        refund: 0,
+    };
+
+    (kasbah, redemption)
+}
+
+#[tokio::test]
+async fn test_redeem() {
+    for packed_public_inputs in [false, true] {
+        let prover = Prover::new(packed_public_inputs);
+        let (mut kasbah, redemption) = setup(&prover).await;
+        let relayer = kasbah.funded_keypair(DENOMINATION);
+        let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+
+        kasbah
+            .redeem(&relayer, &redemption, proof, 1)
+            .await
+            .unwrap();
+        assert_eq!(kasbah.lamports(&redemption.bob).await, DENOMINATION);
+
+        // Same nullifier, submitted by another relayer so the transaction differs
+        let relayer = kasbah.funded_keypair(DENOMINATION);
+        let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+        assert_kasbah_error(
+            kasbah.redeem(&relayer, &redemption, proof, 1).await,
+            KasbahErrors::DoubleSpend,
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_redeem_rejects_forged_proofs() {
+    let prover = Prover::new(false);
+    let (mut kasbah, redemption) = setup(&prover).await;
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+
+    // Proof of another circuit
+    let other_proof = Prover::new(true).prove(&redemption.public_inputs(&relayer.pubkey()));
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &redemption, other_proof, 1).await,
+        KasbahErrors::InvalidProof,
+    );
+
+    // Proof for another recipient
+    let eve = Redemption {
+        bob: Keypair::new().pubkey(),
+        ..redemption
+    };
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &eve, proof, 1).await,
+        KasbahErrors::InvalidProof,
+    );
+
+    // Root the pool never had
+    let unknown_root = Redemption {
+        root: field_element(3),
+        ..eve
+    };
+    let proof = prover.prove(&unknown_root.public_inputs(&relayer.pubkey()));
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &unknown_root, proof, 1).await,
+        KasbahErrors::UnknownRoot,
+    );
+    assert_eq!(kasbah.lamports(&unknown_root.bob).await, 0);
+}
+
+#[tokio::test]
+async fn test_redeem_rejects_unfrozen_verifying_keys() {
+    let prover = Prover::new(false);
+    let (mut kasbah, redemption) = setup(&prover).await;
+    // Same key, complete but never frozen
+    kasbah.upload_verifying_key(&prover, 2, false).await;
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &redemption, proof, 2).await,
+        KasbahErrors::VerifyingKeyNotFrozen,
+    );
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use anchor_lang::{prelude::Rent, Space};
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use kasbah::{KasbahErrors, Nullifier, Treasury, DISCRIMINATOR, NATIVE_SOL_MINT};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const RELAYER_FEE: u64 = DENOMINATION / 10;
+const REFUND: u64 = 1_000_000;
+
+/// Pool of `DENOMINATION` of `mint` under key version 1 holding one deposit, returns the
+/// redemption of that deposit to a fresh bob with a relayer fee
+async fn setup(prover: &Prover, token_program: Option<&Pubkey>) -> (Kasbah, Redemption) {
+    let mut kasbah = Kasbah::new().await;
+    kasbah.upload_verifying_key(prover, 1, true).await;
+    let alice = kasbah.funded_keypair(2 * DENOMINATION);
+
+    let (mint, root) = match token_program {
+        Some(token_program) => {
+            let mint = kasbah.create_mint(token_program, None).await;
+            kasbah.create_pool(mint, DENOMINATION, 1).await.unwrap();
+            kasbah
+                .mint_to(&mint, token_program, &alice.pubkey(), DENOMINATION)
+                .await;
+            let root = kasbah
+                .deposit_token(
+                    &alice,
+                    &mint,
+                    token_program,
+                    field_element(1),
+                    DENOMINATION,
+                    1,
+                )
+                .await
+                .unwrap();
+            (mint, root)
+        }
+        None => {
+            kasbah
+                .create_pool(NATIVE_SOL_MINT, DENOMINATION, 1)
+                .await
+                .unwrap();
+            let root = kasbah
+                .deposit(&alice, field_element(1), DENOMINATION, 1)
+                .await
+                .unwrap();
+            (NATIVE_SOL_MINT, root)
+        }
+    };
+
+    let redemption = Redemption {
+        mint,
+        denomination: DENOMINATION,
+        commitment_pool_id: 1,
+        root,
+        nullifier_hash: field_element(2),
+        bob: Keypair::new().pubkey(),
+        relayer_fee: RELAYER_FEE,
+        refund: 0,
+    };
+
+    (kasbah, redemption)
+}
+
+#[tokio::test]
+async fn test_relayer_fee() {
+    let prover = Prover::new(false);
+    let (mut kasbah, redemption) = setup(&prover, None).await;
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+
+    let treasury: Treasury = kasbah.anchor_account(&treasury_pda()).await;
+    kasbah
+        .redeem(&relayer, &redemption, proof, 1)
+        .await
+        .unwrap();
+
+    // The relayer pays the protocol fee and the nullifier rent, out of its fee
+    let nullifier_rent = Rent::default().minimum_balance(DISCRIMINATOR + Nullifier::INIT_SPACE);
+    assert_eq!(
+        kasbah.lamports(&redemption.bob).await,
+        DENOMINATION - RELAYER_FEE
+    );
+    assert_eq!(
+        kasbah.lamports(&relayer.pubkey()).await,
+        DENOMINATION + RELAYER_FEE - treasury.fee(DENOMINATION) - nullifier_rent
+    );
+}
+
+#[tokio::test]
+async fn test_relayer_fee_is_bound_to_the_proof() {
+    let prover = Prover::new(false);
+    let (mut kasbah, redemption) = setup(&prover, None).await;
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+
+    // Higher fee than bob agreed to
+    let higher_fee = Redemption {
+        relayer_fee: 2 * RELAYER_FEE,
+        ..redemption
+    };
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &higher_fee, proof, 1).await,
+        KasbahErrors::InvalidProof,
+    );
+
+    // Another relayer front-running the transaction
+    let redemption = Redemption {
+        relayer_fee: RELAYER_FEE,
+        ..higher_fee
+    };
+    let other_relayer = kasbah.funded_keypair(DENOMINATION);
+    assert_kasbah_error(
+        kasbah.redeem(&other_relayer, &redemption, proof, 1).await,
+        KasbahErrors::InvalidProof,
+    );
+
+    // Valid proof, but for more than the pool pays out
+    let excessive_fee = Redemption {
+        relayer_fee: DENOMINATION + 1,
+        ..redemption
+    };
+    let proof = prover.prove(&excessive_fee.public_inputs(&relayer.pubkey()));
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &excessive_fee, proof, 1).await,
+        KasbahErrors::InvalidRelayerFee,
+    );
+
+    // Refunds are only paid alongside tokens
+    let refund = Redemption {
+        relayer_fee: RELAYER_FEE,
+        refund: REFUND,
+        ..excessive_fee
+    };
+    let proof = prover.prove(&refund.public_inputs(&relayer.pubkey()));
+    assert_kasbah_error(
+        kasbah.redeem(&relayer, &refund, proof, 1).await,
+        KasbahErrors::InvalidRefund,
+    );
+}
+
+#[tokio::test]
+async fn test_relayer_fee_and_refund_of_token_redemptions() {
+    let prover = Prover::new(false);
+    let token_program = spl_token_2022::ID;
+    let (mut kasbah, redemption) = setup(&prover, Some(&token_program)).await;
+    let redemption = Redemption {
+        refund: REFUND,
+        ..redemption
+    };
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+
+    // Refund bob didn't agree to
+    let no_refund = Redemption {
+        refund: 0,
+        ..redemption
+    };
+    assert_kasbah_error(
+        kasbah
+            .redeem_token(&relayer, &no_refund, proof, 1, &token_program)
+            .await,
+        KasbahErrors::InvalidProof,
+    );
+
+    let redemption = Redemption {
+        refund: REFUND,
+        ..no_refund
+    };
+    kasbah
+        .redeem_token(&relayer, &redemption, proof, 1, &token_program)
+        .await
+        .unwrap();
+
+    let mint = &redemption.mint;
+    let bob_token_account = associated_token_address(&redemption.bob, mint, &token_program);
+    let relayer_token_account = associated_token_address(&relayer.pubkey(), mint, &token_program);
+    assert_eq!(
+        kasbah.token_balance(&bob_token_account).await,
+        DENOMINATION - RELAYER_FEE
+    );
+    assert_eq!(
+        kasbah.token_balance(&relayer_token_account).await,
+        RELAYER_FEE
+    );
+    assert_eq!(kasbah.lamports(&redemption.bob).await, REFUND);
+}
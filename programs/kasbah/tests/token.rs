@@ -0,0 +1,148 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_spl::{token::spl_token, token_2022::spl_token_2022};
+use common::*;
+use kasbah::KasbahErrors;
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// Pool of `DENOMINATION` base units of a new mint of `token_program` under key version 1,
+/// with alice holding two deposits worth of tokens
+async fn setup(
+    prover: &Prover,
+    token_program: &Pubkey,
+    transfer_fee_bps: Option<u16>,
+) -> (Kasbah, Pubkey, Keypair) {
+    let mut kasbah = Kasbah::new().await;
+    let mint = kasbah.create_mint(token_program, transfer_fee_bps).await;
+    kasbah.upload_verifying_key(prover, 1, true).await;
+    kasbah.create_pool(mint, DENOMINATION, 1).await.unwrap();
+
+    let alice = kasbah.funded_keypair(DENOMINATION);
+    kasbah
+        .mint_to(&mint, token_program, &alice.pubkey(), 2 * DENOMINATION)
+        .await;
+
+    (kasbah, mint, alice)
+}
+
+#[tokio::test]
+async fn test_deposit_and_redeem_token() {
+    let prover = Prover::new(false);
+    for token_program in [spl_token::ID, spl_token_2022::ID] {
+        let (mut kasbah, mint, alice) = setup(&prover, &token_program, None).await;
+        let vault = associated_token_address(
+            &commitment_pool_pda(&mint, DENOMINATION, 1),
+            &mint,
+            &token_program,
+        );
+
+        let root = kasbah
+            .deposit_token(
+                &alice,
+                &mint,
+                &token_program,
+                field_element(1),
+                DENOMINATION,
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(kasbah.token_balance(&vault).await, DENOMINATION);
+        assert_eq!(
+            kasbah
+                .token_balance(&associated_token_address(
+                    &alice.pubkey(),
+                    &mint,
+                    &token_program
+                ))
+                .await,
+            DENOMINATION
+        );
+
+        let redemption = Redemption {
+            mint,
+            denomination: DENOMINATION,
+            commitment_pool_id: 1,
+            root,
+            nullifier_hash: field_element(3),
+            bob: Keypair::new().pubkey(),
+            relayer_fee: 0,
+            refund: 0,
+        };
+        let relayer = kasbah.funded_keypair(DENOMINATION);
+        let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+        kasbah
+            .redeem_token(&relayer, &redemption, proof, 1, &token_program)
+            .await
+            .unwrap();
+
+        let bob_token_account = associated_token_address(&redemption.bob, &mint, &token_program);
+        assert_eq!(kasbah.token_balance(&bob_token_account).await, DENOMINATION);
+        assert_eq!(kasbah.token_balance(&vault).await, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_redeem_token_rejects_other_mints() {
+    let prover = Prover::new(false);
+    let token_program = spl_token_2022::ID;
+    let (mut kasbah, mint, alice) = setup(&prover, &token_program, None).await;
+    let root = kasbah
+        .deposit_token(
+            &alice,
+            &mint,
+            &token_program,
+            field_element(1),
+            DENOMINATION,
+            1,
+        )
+        .await
+        .unwrap();
+
+    // No pool of that mint and denomination
+    let other_mint = kasbah.create_mint(&token_program, None).await;
+    let redemption = Redemption {
+        mint: other_mint,
+        denomination: DENOMINATION,
+        commitment_pool_id: 1,
+        root,
+        nullifier_hash: field_element(2),
+        bob: Keypair::new().pubkey(),
+        relayer_fee: 0,
+        refund: 0,
+    };
+    let relayer = kasbah.funded_keypair(DENOMINATION);
+    let proof = prover.prove(&redemption.public_inputs(&relayer.pubkey()));
+    assert_anchor_error(
+        kasbah
+            .redeem_token(&relayer, &redemption, proof, 1, &token_program)
+            .await,
+        ErrorCode::AccountOwnedByWrongProgram,
+    );
+}
+
+#[tokio::test]
+async fn test_deposit_token_rejects_transfer_fees() {
+    let prover = Prover::new(false);
+    let token_program = spl_token_2022::ID;
+    let (mut kasbah, mint, alice) = setup(&prover, &token_program, Some(100)).await;
+
+    assert_kasbah_error(
+        kasbah
+            .deposit_token(
+                &alice,
+                &mint,
+                &token_program,
+                field_element(1),
+                DENOMINATION,
+                1,
+            )
+            .await,
+        KasbahErrors::UnsupportedMint,
+    );
+}
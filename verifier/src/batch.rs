@@ -0,0 +1,192 @@
+use num_bigint::BigUint;
+use solana_sha256_hasher::hashv;
+
+use crate::{
+    errors::Groth16Error,
+    prepare_public_inputs,
+    syscalls::{g1_addition, g1_decompress, g1_multiplication, g2_decompress, pairing},
+    Groth16Verifyingkey,
+};
+
+#[derive(PartialEq, Eq, Debug)]
+struct BatchedProof<'a, const NR_INPUTS: usize> {
+    proof_a: [u8; 64],
+    proof_b: [u8; 128],
+    proof_c: [u8; 64],
+    public_inputs: &'a [[u8; 32]; NR_INPUTS],
+}
+
+/// Verifies several proofs against the same verifying key with a single multi-pairing.
+///
+/// Every proof is weighted by a 128-bit scalar `r_i` derived from all the proofs and public
+/// inputs of the batch, and their equations are folded into a single one of `n + 3` pairings
+/// instead of `4 * n`:
+///
+/// `prod(e(r_i * -A_i, B_i)) * e(sum(r_i * L_i), gamma) * e(sum(r_i * C_i), delta) *
+/// e(sum(r_i) * alpha, beta) == 1`
+///
+/// An invalid proof passes with probability at most 2^-128, but the batch doesn't tell
+/// which proof failed.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Groth16BatchVerifier<'a, const NR_INPUTS: usize> {
+    proofs: Vec<BatchedProof<'a, NR_INPUTS>>,
+    verifyingkey: &'a Groth16Verifyingkey<'a>,
+}
+
+impl<'a, const NR_INPUTS: usize> Groth16BatchVerifier<'a, NR_INPUTS> {
+    pub fn new(
+        verifyingkey: &'a Groth16Verifyingkey<'a>,
+    ) -> Result<Groth16BatchVerifier<'a, NR_INPUTS>, Groth16Error> {
+        if verifyingkey.nr_pubinputs != NR_INPUTS {
+            return Err(Groth16Error::IncompatibleVerifyingKeyWithNrPublicInputs);
+        }
+
+        if NR_INPUTS + 1 != verifyingkey.vk_ic.len() {
+            return Err(Groth16Error::InvalidPublicInputsLength);
+        }
+
+        Ok(Groth16BatchVerifier {
+            proofs: Vec::new(),
+            verifyingkey,
+        })
+    }
+
+    /// Adds a proof to the batch, `proof_a` is expected negated like in `Groth16Verifier::new`
+    pub fn push(
+        &mut self,
+        proof_a: &[u8; 64],
+        proof_b: &[u8; 128],
+        proof_c: &[u8; 64],
+        public_inputs: &'a [[u8; 32]; NR_INPUTS],
+    ) {
+        self.proofs.push(BatchedProof {
+            proof_a: *proof_a,
+            proof_b: *proof_b,
+            proof_c: *proof_c,
+            public_inputs,
+        });
+    }
+
+    /// Same as `push` with compressed points, see `Groth16Verifier::new_compressed`
+    pub fn push_compressed(
+        &mut self,
+        proof_a: &[u8; 32],
+        proof_b: &[u8; 64],
+        proof_c: &[u8; 32],
+        public_inputs: &'a [[u8; 32]; NR_INPUTS],
+    ) -> Result<(), Groth16Error> {
+        let proof_a = g1_decompress(proof_a).map_err(|_| Groth16Error::DecompressingG1Failed)?;
+        let proof_b = g2_decompress(proof_b).map_err(|_| Groth16Error::DecompressingG2Failed)?;
+        let proof_c = g1_decompress(proof_c).map_err(|_| Groth16Error::DecompressingG1Failed)?;
+
+        self.push(&proof_a, &proof_b, &proof_c, public_inputs);
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// Verifies all the proofs of the batch, and checks that public inputs are smaller than
+    /// field size.
+    pub fn verify(&self) -> Result<bool, Groth16Error> {
+        self.verify_common::<true>()
+    }
+
+    /// Verifies all the proofs of the batch, and does not check that public inputs are
+    /// smaller than field size.
+    pub fn verify_unchecked(&self) -> Result<bool, Groth16Error> {
+        self.verify_common::<false>()
+    }
+
+    /// Fiat-Shamir scalars, binding every proof to the whole batch so that they can't be
+    /// chosen to cancel each other out
+    fn scalars(&self) -> Vec<[u8; 32]> {
+        let mut transcript: Vec<&[u8]> = Vec::with_capacity(self.proofs.len() * (3 + NR_INPUTS));
+        for proof in &self.proofs {
+            transcript.extend([
+                proof.proof_a.as_slice(),
+                proof.proof_b.as_slice(),
+                proof.proof_c.as_slice(),
+            ]);
+            transcript.extend(proof.public_inputs.iter().map(|input| input.as_slice()));
+        }
+        let seed = hashv(&transcript).to_bytes();
+
+        (0..self.proofs.len() as u64)
+            .map(|i| {
+                let mut scalar = [0u8; 32];
+                scalar[16..].copy_from_slice(&hashv(&[&seed, &i.to_le_bytes()]).to_bytes()[..16]);
+                // Never 0, which would drop the proof from the batch
+                scalar[31] |= 1;
+                scalar
+            })
+            .collect()
+    }
+
+    fn verify_common<const CHECK: bool>(&self) -> Result<bool, Groth16Error> {
+        if self.proofs.is_empty() {
+            return Err(Groth16Error::EmptyBatch);
+        }
+
+        let scalars = self.scalars();
+        let mut pairing_input = Vec::with_capacity((self.proofs.len() + 3) * 192);
+        let mut prepared_public_inputs = [0u8; 64];
+        let mut proof_c = [0u8; 64];
+        let mut scalar_sum = BigUint::default();
+
+        for (proof, scalar) in self.proofs.iter().zip(&scalars) {
+            let proof_inputs =
+                prepare_public_inputs::<CHECK, NR_INPUTS>(proof.public_inputs, self.verifyingkey)?;
+
+            pairing_input.extend(scalar_mul(&proof.proof_a, scalar)?);
+            pairing_input.extend(proof.proof_b);
+            prepared_public_inputs =
+                point_add(&prepared_public_inputs, &scalar_mul(&proof_inputs, scalar)?)?;
+            proof_c = point_add(&proof_c, &scalar_mul(&proof.proof_c, scalar)?)?;
+            scalar_sum += BigUint::from_bytes_be(scalar);
+        }
+
+        // At most 2^128 * len, no reduction needed to fit in 32 bytes
+        let scalar_sum = scalar_sum.to_bytes_be();
+        let mut alpha_scalar = [0u8; 32];
+        alpha_scalar[32 - scalar_sum.len()..].copy_from_slice(&scalar_sum);
+        let alpha = scalar_mul(&self.verifyingkey.vk_alpha_g1, &alpha_scalar)?;
+
+        for (g1, g2) in [
+            (&prepared_public_inputs, &self.verifyingkey.vk_gamme_g2),
+            (&proof_c, &self.verifyingkey.vk_delta_g2),
+            (&alpha, &self.verifyingkey.vk_beta_g2),
+        ] {
+            pairing_input.extend(g1);
+            pairing_input.extend(g2);
+        }
+
+        let pairing_res =
+            pairing(&pairing_input).map_err(|_| Groth16Error::ProofVerificationFailed)?;
+
+        if pairing_res[31] != 1 {
+            return Err(Groth16Error::ProofVerificationFailed);
+        }
+        Ok(true)
+    }
+}
+
+fn scalar_mul(point: &[u8; 64], scalar: &[u8; 32]) -> Result<[u8; 64], Groth16Error> {
+    let mut input = [0u8; 96];
+    input[..64].copy_from_slice(point);
+    input[64..].copy_from_slice(scalar);
+    g1_multiplication(&input).map_err(|_| Groth16Error::PreparingInputsG1MulFailed)
+}
+
+fn point_add(a: &[u8; 64], b: &[u8; 64]) -> Result<[u8; 64], Groth16Error> {
+    let mut input = [0u8; 128];
+    input[..64].copy_from_slice(a);
+    input[64..].copy_from_slice(b);
+    g1_addition(&input).map_err(|_| Groth16Error::PreparingInputsG1AdditionFailed)
+}
@@ -22,4 +22,8 @@ pub enum Groth16Error {
     DecompressingG2Failed,
     #[error("PublicInputGreaterThenFieldSize")]
     PublicInputGreaterThenFieldSize,
+    #[error("EmptyBatch")]
+    EmptyBatch,
+    #[error("InvalidVerifyingKeyPoint")]
+    InvalidVerifyingKeyPoint,
 }
@@ -1,9 +1,18 @@
+pub mod batch;
 pub mod errors;
+#[cfg(all(not(target_os = "solana"), feature = "native"))]
+mod native;
+pub mod prepared;
+mod syscalls;
+
+pub use batch::Groth16BatchVerifier;
+pub use prepared::PreparedVerifyingKey;
+
+#[cfg(test)]
+mod tests;
 
-use ark_ff::PrimeField;
 use errors::Groth16Error;
-use num_bigint::BigUint;
-use solana_bn254::prelude::*;
+use syscalls::{g1_addition, g1_decompress, g1_multiplication, g2_decompress, pairing};
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct Groth16Verifyingkey<'a> {
@@ -17,9 +26,9 @@ pub struct Groth16Verifyingkey<'a> {
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct Groth16Verifier<'a, const NR_INPUTS: usize> {
-    proof_a: &'a [u8; 64],
-    proof_b: &'a [u8; 128],
-    proof_c: &'a [u8; 64],
+    proof_a: [u8; 64],
+    proof_b: [u8; 128],
+    proof_c: [u8; 64],
     public_inputs: &'a [[u8; 32]; NR_INPUTS],
     prepared_public_inputs: [u8; 64],
     verifyingkey: &'a Groth16Verifyingkey<'a>,
@@ -27,9 +36,9 @@ pub struct Groth16Verifier<'a, const NR_INPUTS: usize> {
 
 impl<const NR_INPUTS: usize> Groth16Verifier<'_, NR_INPUTS> {
     pub fn new<'a>(
-        proof_a: &'a [u8; 64],
-        proof_b: &'a [u8; 128],
-        proof_c: &'a [u8; 64],
+        proof_a: &[u8; 64],
+        proof_b: &[u8; 128],
+        proof_c: &[u8; 64],
         public_inputs: &'a [[u8; 32]; NR_INPUTS],
         verifyingkey: &'a Groth16Verifyingkey<'a>,
     ) -> Result<Groth16Verifier<'a, NR_INPUTS>, Groth16Error> {
@@ -45,39 +54,43 @@ impl<const NR_INPUTS: usize> Groth16Verifier<'_, NR_INPUTS> {
             return Err(Groth16Error::InvalidG1Length);
         }
 
+        if verifyingkey.nr_pubinputs != NR_INPUTS {
+            return Err(Groth16Error::IncompatibleVerifyingKeyWithNrPublicInputs);
+        }
+
         if public_inputs.len() + 1 != verifyingkey.vk_ic.len() {
             return Err(Groth16Error::InvalidPublicInputsLength);
         }
 
         Ok(Groth16Verifier {
-            proof_a,
-            proof_b,
-            proof_c,
+            proof_a: *proof_a,
+            proof_b: *proof_b,
+            proof_c: *proof_c,
             public_inputs,
             prepared_public_inputs: [0u8; 64],
             verifyingkey,
         })
     }
 
-    pub fn prepare_inputs<const CHECK: bool>(&mut self) -> Result<(), Groth16Error> {
-        let mut prepared_public_inputs = self.verifyingkey.vk_ic[0];
-
-        for (i, input) in self.public_inputs.iter().enumerate() {
-            if CHECK && !is_less_than_bn254_field_size_be(input) {
-                return Err(Groth16Error::PublicInputGreaterThenFieldSize);
-            }
-            let mul_res = alt_bn128_multiplication(
-                &[&self.verifyingkey.vk_ic[i + 1][..], &input[..]].concat(),
-            )
-            .map_err(|_| Groth16Error::PreparingInputsG1MulFailed)?;
-            prepared_public_inputs =
-                alt_bn128_addition(&[&mul_res[..], &prepared_public_inputs[..]].concat())
-                    .map_err(|_| Groth16Error::PreparingInputsG1AdditionFailed)?[..]
-                    .try_into()
-                    .map_err(|_| Groth16Error::PreparingInputsG1AdditionFailed)?;
-        }
+    /// Same as `new` with compressed points, `proof_a` is still expected negated.
+    /// Points are decompressed with the alt_bn128 compression syscalls
+    pub fn new_compressed<'a>(
+        proof_a: &[u8; 32],
+        proof_b: &[u8; 64],
+        proof_c: &[u8; 32],
+        public_inputs: &'a [[u8; 32]; NR_INPUTS],
+        verifyingkey: &'a Groth16Verifyingkey<'a>,
+    ) -> Result<Groth16Verifier<'a, NR_INPUTS>, Groth16Error> {
+        let proof_a = g1_decompress(proof_a).map_err(|_| Groth16Error::DecompressingG1Failed)?;
+        let proof_b = g2_decompress(proof_b).map_err(|_| Groth16Error::DecompressingG2Failed)?;
+        let proof_c = g1_decompress(proof_c).map_err(|_| Groth16Error::DecompressingG1Failed)?;
 
-        self.prepared_public_inputs = prepared_public_inputs;
+        Groth16Verifier::new(&proof_a, &proof_b, &proof_c, public_inputs, verifyingkey)
+    }
+
+    pub fn prepare_inputs<const CHECK: bool>(&mut self) -> Result<(), Groth16Error> {
+        self.prepared_public_inputs =
+            prepare_public_inputs::<CHECK, NR_INPUTS>(self.public_inputs, self.verifyingkey)?;
 
         Ok(())
     }
@@ -97,7 +110,9 @@ impl<const NR_INPUTS: usize> Groth16Verifier<'_, NR_INPUTS> {
     fn verify_common<const CHECK: bool>(&mut self) -> Result<bool, Groth16Error> {
         self.prepare_inputs::<CHECK>()?;
 
-        let pairing_input = [
+        let mut pairing_input = [0u8; 768];
+        let mut offset = 0;
+        for bytes in [
             self.proof_a.as_slice(),
             self.proof_b.as_slice(),
             self.prepared_public_inputs.as_slice(),
@@ -106,11 +121,13 @@ impl<const NR_INPUTS: usize> Groth16Verifier<'_, NR_INPUTS> {
             self.verifyingkey.vk_delta_g2.as_slice(),
             self.verifyingkey.vk_alpha_g1.as_slice(),
             self.verifyingkey.vk_beta_g2.as_slice(),
-        ]
-        .concat();
+        ] {
+            pairing_input[offset..offset + bytes.len()].copy_from_slice(bytes);
+            offset += bytes.len();
+        }
 
-        let pairing_res = alt_bn128_pairing(pairing_input.as_slice())
-            .map_err(|_| Groth16Error::ProofVerificationFailed)?;
+        let pairing_res =
+            pairing(&pairing_input).map_err(|_| Groth16Error::ProofVerificationFailed)?;
 
         if pairing_res[31] != 1 {
             return Err(Groth16Error::ProofVerificationFailed);
@@ -119,7 +136,40 @@ impl<const NR_INPUTS: usize> Groth16Verifier<'_, NR_INPUTS> {
     }
 }
 
+/// `vk_ic[0] + sum(public_inputs[i] * vk_ic[i + 1])`
+pub(crate) fn prepare_public_inputs<const CHECK: bool, const NR_INPUTS: usize>(
+    public_inputs: &[[u8; 32]; NR_INPUTS],
+    verifyingkey: &Groth16Verifyingkey,
+) -> Result<[u8; 64], Groth16Error> {
+    let mut mul_input = [0u8; 96];
+    let mut add_input = [0u8; 128];
+    add_input[64..].copy_from_slice(&verifyingkey.vk_ic[0]);
+
+    for (i, input) in public_inputs.iter().enumerate() {
+        if CHECK && !is_less_than_bn254_field_size_be(input) {
+            return Err(Groth16Error::PublicInputGreaterThenFieldSize);
+        }
+        mul_input[..64].copy_from_slice(&verifyingkey.vk_ic[i + 1]);
+        mul_input[64..].copy_from_slice(input);
+        let mul_res =
+            g1_multiplication(&mul_input).map_err(|_| Groth16Error::PreparingInputsG1MulFailed)?;
+
+        add_input[..64].copy_from_slice(&mul_res);
+        let add_res =
+            g1_addition(&add_input).map_err(|_| Groth16Error::PreparingInputsG1AdditionFailed)?;
+        add_input[64..].copy_from_slice(&add_res);
+    }
+
+    Ok(add_input[64..].try_into().unwrap())
+}
+
+/// BN254 scalar field modulus, big-endian
+const BN254_FR_MODULUS_BE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
 pub fn is_less_than_bn254_field_size_be(bytes: &[u8; 32]) -> bool {
-    let bigint = BigUint::from_bytes_be(bytes);
-    bigint < ark_bn254::Fr::MODULUS.into()
+    // Big-endian byte arrays compare lexicographically like the integers they encode
+    *bytes < BN254_FR_MODULUS_BE
 }
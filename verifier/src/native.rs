@@ -0,0 +1,153 @@
+//! Arkworks implementation of the alt_bn128 operations used by the verifiers.
+//!
+//! Mirrors the encoding and validation of the syscalls: big-endian coordinates, G2
+//! coordinates ordered `(c1, c0)`, all-zero bytes for the identity, unreduced 32-byte
+//! scalars, G2 points checked to be in the subgroup. Proofs that verify here verify
+//! on-chain, and fail with the same `Groth16Error` otherwise.
+
+use ark_bn254::{Bn254, Fq12, G1Affine, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::{BigInteger256, One};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use solana_bn254::{compression::prelude::AltBn128CompressionError, prelude::*};
+
+/// Reverses every `CHUNK_SIZE` bytes, big-endian coordinates to arkworks' little-endian
+fn convert_endianness<const CHUNK_SIZE: usize, const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut converted = [0u8; N];
+    for (to, from) in converted
+        .chunks_exact_mut(CHUNK_SIZE)
+        .zip(bytes.chunks_exact(CHUNK_SIZE))
+    {
+        to.copy_from_slice(from);
+        to.reverse();
+    }
+    converted
+}
+
+fn g1_from_be_bytes(bytes: &[u8]) -> Result<G1Affine, AltBn128Error> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(G1Affine::zero());
+    }
+    let point = G1Affine::deserialize_with_mode(
+        convert_endianness::<32, 64>(bytes).as_slice(),
+        Compress::No,
+        Validate::Yes,
+    )
+    .map_err(|_| AltBn128Error::InvalidInputData)?;
+
+    match point.is_on_curve() {
+        true => Ok(point),
+        false => Err(AltBn128Error::GroupError),
+    }
+}
+
+fn g2_from_be_bytes(bytes: &[u8]) -> Result<G2Affine, AltBn128Error> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(G2Affine::zero());
+    }
+    // (x.c1, x.c0, y.c1, y.c0) to arkworks' (x.c0, x.c1, y.c0, y.c1)
+    let mut le_bytes = convert_endianness::<32, 128>(bytes);
+    le_bytes[..64].rotate_left(32);
+    le_bytes[64..].rotate_left(32);
+    let point = G2Affine::deserialize_with_mode(le_bytes.as_slice(), Compress::No, Validate::Yes)
+        .map_err(|_| AltBn128Error::InvalidInputData)?;
+
+    match point.is_on_curve() {
+        true => Ok(point),
+        false => Err(AltBn128Error::GroupError),
+    }
+}
+
+fn g1_to_be_bytes(point: &G1Affine) -> Result<[u8; 64], AltBn128Error> {
+    let mut bytes = [0u8; 64];
+    point
+        .x
+        .serialize_with_mode(&mut bytes[..32], Compress::No)
+        .map_err(|_| AltBn128Error::InvalidInputData)?;
+    point
+        .y
+        .serialize_with_mode(&mut bytes[32..], Compress::No)
+        .map_err(|_| AltBn128Error::InvalidInputData)?;
+
+    Ok(convert_endianness::<32, 64>(&bytes))
+}
+
+pub(crate) fn g1_addition(
+    input: &[u8; ALT_BN128_ADDITION_INPUT_LEN],
+) -> Result<[u8; ALT_BN128_ADDITION_OUTPUT_LEN], AltBn128Error> {
+    let p = g1_from_be_bytes(&input[..64])?;
+    let q = g1_from_be_bytes(&input[64..])?;
+
+    g1_to_be_bytes(&(p + q).into())
+}
+
+pub(crate) fn g1_multiplication(
+    input: &[u8; ALT_BN128_MULTIPLICATION_INPUT_LEN],
+) -> Result<[u8; ALT_BN128_MULTIPLICATION_OUTPUT_LEN], AltBn128Error> {
+    let p = g1_from_be_bytes(&input[..64])?;
+    // Not reduced modulo the group order
+    let scalar = BigInteger256::deserialize_uncompressed_unchecked(
+        convert_endianness::<32, 32>(&input[64..]).as_slice(),
+    )
+    .map_err(|_| AltBn128Error::InvalidInputData)?;
+
+    g1_to_be_bytes(&p.mul_bigint(scalar).into())
+}
+
+pub(crate) fn pairing(input: &[u8]) -> Result<[u8; ALT_BN128_PAIRING_OUTPUT_LEN], AltBn128Error> {
+    let pairs = input.chunks_exact(ALT_BN128_PAIRING_ELEMENT_LEN);
+    if !pairs.remainder().is_empty() {
+        return Err(AltBn128Error::SliceOutOfBounds);
+    }
+    let mut g1_points = Vec::with_capacity(pairs.len());
+    let mut g2_points = Vec::with_capacity(pairs.len());
+    for pair in pairs {
+        g1_points.push(g1_from_be_bytes(&pair[..64])?);
+        g2_points.push(g2_from_be_bytes(&pair[64..])?);
+    }
+
+    let mut result = [0u8; ALT_BN128_PAIRING_OUTPUT_LEN];
+    if Bn254::multi_pairing(g1_points, g2_points).0 == Fq12::one() {
+        result[31] = 1;
+    }
+
+    Ok(result)
+}
+
+pub(crate) fn g1_decompress(input: &[u8; 32]) -> Result<[u8; 64], AltBn128CompressionError> {
+    if *input == [0u8; 32] {
+        return Ok([0u8; 64]);
+    }
+    let point = G1Affine::deserialize_with_mode(
+        convert_endianness::<32, 32>(input).as_slice(),
+        Compress::Yes,
+        Validate::No,
+    )
+    .map_err(|_| AltBn128CompressionError::G1DecompressionFailed)?;
+
+    g1_to_be_bytes(&point).map_err(|_| AltBn128CompressionError::G1DecompressionFailed)
+}
+
+pub(crate) fn g2_decompress(input: &[u8; 64]) -> Result<[u8; 128], AltBn128CompressionError> {
+    if *input == [0u8; 64] {
+        return Ok([0u8; 128]);
+    }
+    let point = G2Affine::deserialize_with_mode(
+        convert_endianness::<64, 64>(input).as_slice(),
+        Compress::Yes,
+        Validate::No,
+    )
+    .map_err(|_| AltBn128CompressionError::G2DecompressionFailed)?;
+
+    let mut bytes = [0u8; 128];
+    point
+        .x
+        .serialize_with_mode(&mut bytes[..64], Compress::No)
+        .map_err(|_| AltBn128CompressionError::G2DecompressionFailed)?;
+    point
+        .y
+        .serialize_with_mode(&mut bytes[64..], Compress::No)
+        .map_err(|_| AltBn128CompressionError::G2DecompressionFailed)?;
+
+    Ok(convert_endianness::<64, 128>(&bytes))
+}
@@ -0,0 +1,192 @@
+use crate::{
+    errors::Groth16Error,
+    is_less_than_bn254_field_size_be,
+    syscalls::{g1_addition, g1_decompress, g1_multiplication, g2_decompress, pairing},
+    Groth16Verifyingkey,
+};
+
+/// `e(-A, B) * e(L, gamma) * e(C, delta) * e(alpha, beta)`, 4 pairs of a G1 and a G2 point
+const PAIRING_INPUT_LEN: usize = 4 * 192;
+const PROOF_A: usize = 0;
+const PROOF_B: usize = 64;
+const PREPARED_PUBLIC_INPUTS: usize = 192;
+const VK_GAMMA_G2: usize = 256;
+const PROOF_C: usize = 384;
+const VK_DELTA_G2: usize = 448;
+const VK_ALPHA_G1: usize = 576;
+const VK_BETA_G2: usize = 640;
+
+/// (1, 2)
+const G1_GENERATOR: [u8; 64] = {
+    let mut generator = [0u8; 64];
+    generator[31] = 1;
+    generator[63] = 2;
+    generator
+};
+
+/// Verifying key checked once, with its points laid out in the pairing input of `verify`.
+///
+/// Unlike `Groth16Verifier`, verification only uses stack buffers: a single key can be
+/// loaded once and verify any number of proofs without allocating.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PreparedVerifyingKey<const NR_INPUTS: usize> {
+    vk_ic_0: [u8; 64],
+    vk_ic: [[u8; 64]; NR_INPUTS],
+    /// Key points in place, the proof and prepared public inputs slots left zeroed
+    pairing_input: [u8; PAIRING_INPUT_LEN],
+}
+
+impl<const NR_INPUTS: usize> PreparedVerifyingKey<NR_INPUTS> {
+    /// Checks that `verifyingkey` is a key for `NR_INPUTS` public inputs and that all its
+    /// points are valid and not the identity
+    pub fn new(verifyingkey: &Groth16Verifyingkey) -> Result<Self, Groth16Error> {
+        let prepared = Self::new_unchecked(verifyingkey)?;
+
+        for point in [&verifyingkey.vk_alpha_g1]
+            .into_iter()
+            .chain(verifyingkey.vk_ic)
+        {
+            if *point == [0u8; 64] {
+                return Err(Groth16Error::InvalidVerifyingKeyPoint);
+            }
+            // Adding the identity fails on points not on the curve
+            let mut input = [0u8; 128];
+            input[..64].copy_from_slice(point);
+            g1_addition(&input).map_err(|_| Groth16Error::InvalidVerifyingKeyPoint)?;
+        }
+
+        // The pairing fails on G2 points not on the curve or not in the subgroup
+        let mut input = [0u8; 3 * 192];
+        for (pair, point) in input.chunks_exact_mut(192).zip([
+            &verifyingkey.vk_beta_g2,
+            &verifyingkey.vk_gamme_g2,
+            &verifyingkey.vk_delta_g2,
+        ]) {
+            if *point == [0u8; 128] {
+                return Err(Groth16Error::InvalidVerifyingKeyPoint);
+            }
+            pair[..64].copy_from_slice(&G1_GENERATOR);
+            pair[64..].copy_from_slice(point);
+        }
+        pairing(&input).map_err(|_| Groth16Error::InvalidVerifyingKeyPoint)?;
+
+        Ok(prepared)
+    }
+
+    /// Same as `new` without validating the points, for keys that were already checked,
+    /// e.g. when they were stored
+    pub fn new_unchecked(verifyingkey: &Groth16Verifyingkey) -> Result<Self, Groth16Error> {
+        if verifyingkey.nr_pubinputs != NR_INPUTS {
+            return Err(Groth16Error::IncompatibleVerifyingKeyWithNrPublicInputs);
+        }
+
+        let Some((vk_ic_0, vk_ic)) = verifyingkey.vk_ic.split_first() else {
+            return Err(Groth16Error::InvalidPublicInputsLength);
+        };
+        let vk_ic = vk_ic
+            .try_into()
+            .map_err(|_| Groth16Error::InvalidPublicInputsLength)?;
+
+        let mut pairing_input = [0u8; PAIRING_INPUT_LEN];
+        pairing_input[VK_GAMMA_G2..VK_GAMMA_G2 + 128].copy_from_slice(&verifyingkey.vk_gamme_g2);
+        pairing_input[VK_DELTA_G2..VK_DELTA_G2 + 128].copy_from_slice(&verifyingkey.vk_delta_g2);
+        pairing_input[VK_ALPHA_G1..VK_ALPHA_G1 + 64].copy_from_slice(&verifyingkey.vk_alpha_g1);
+        pairing_input[VK_BETA_G2..VK_BETA_G2 + 128].copy_from_slice(&verifyingkey.vk_beta_g2);
+
+        Ok(PreparedVerifyingKey {
+            vk_ic_0: *vk_ic_0,
+            vk_ic,
+            pairing_input,
+        })
+    }
+
+    /// Verifies the proof, and checks that public inputs are smaller than field size.
+    /// `proof_a` is expected negated
+    pub fn verify(
+        &self,
+        proof_a: &[u8; 64],
+        proof_b: &[u8; 128],
+        proof_c: &[u8; 64],
+        public_inputs: &[[u8; 32]; NR_INPUTS],
+    ) -> Result<bool, Groth16Error> {
+        self.verify_common::<true>(proof_a, proof_b, proof_c, public_inputs)
+    }
+
+    /// Verifies the proof, and does not check that public inputs are smaller than field
+    /// size.
+    pub fn verify_unchecked(
+        &self,
+        proof_a: &[u8; 64],
+        proof_b: &[u8; 128],
+        proof_c: &[u8; 64],
+        public_inputs: &[[u8; 32]; NR_INPUTS],
+    ) -> Result<bool, Groth16Error> {
+        self.verify_common::<false>(proof_a, proof_b, proof_c, public_inputs)
+    }
+
+    /// Same as `verify` with compressed points, see `Groth16Verifier::new_compressed`
+    pub fn verify_compressed(
+        &self,
+        proof_a: &[u8; 32],
+        proof_b: &[u8; 64],
+        proof_c: &[u8; 32],
+        public_inputs: &[[u8; 32]; NR_INPUTS],
+    ) -> Result<bool, Groth16Error> {
+        let proof_a = g1_decompress(proof_a).map_err(|_| Groth16Error::DecompressingG1Failed)?;
+        let proof_b = g2_decompress(proof_b).map_err(|_| Groth16Error::DecompressingG2Failed)?;
+        let proof_c = g1_decompress(proof_c).map_err(|_| Groth16Error::DecompressingG1Failed)?;
+
+        self.verify(&proof_a, &proof_b, &proof_c, public_inputs)
+    }
+
+    fn prepare_inputs<const CHECK: bool>(
+        &self,
+        public_inputs: &[[u8; 32]; NR_INPUTS],
+    ) -> Result<[u8; 64], Groth16Error> {
+        let mut mul_input = [0u8; 96];
+        let mut add_input = [0u8; 128];
+        add_input[64..].copy_from_slice(&self.vk_ic_0);
+
+        for (point, input) in self.vk_ic.iter().zip(public_inputs) {
+            if CHECK && !is_less_than_bn254_field_size_be(input) {
+                return Err(Groth16Error::PublicInputGreaterThenFieldSize);
+            }
+            mul_input[..64].copy_from_slice(point);
+            mul_input[64..].copy_from_slice(input);
+            let mul_res = g1_multiplication(&mul_input)
+                .map_err(|_| Groth16Error::PreparingInputsG1MulFailed)?;
+
+            add_input[..64].copy_from_slice(&mul_res);
+            let add_res = g1_addition(&add_input)
+                .map_err(|_| Groth16Error::PreparingInputsG1AdditionFailed)?;
+            add_input[64..].copy_from_slice(&add_res);
+        }
+
+        Ok(add_input[64..].try_into().unwrap())
+    }
+
+    fn verify_common<const CHECK: bool>(
+        &self,
+        proof_a: &[u8; 64],
+        proof_b: &[u8; 128],
+        proof_c: &[u8; 64],
+        public_inputs: &[[u8; 32]; NR_INPUTS],
+    ) -> Result<bool, Groth16Error> {
+        let prepared_public_inputs = self.prepare_inputs::<CHECK>(public_inputs)?;
+
+        let mut pairing_input = self.pairing_input;
+        pairing_input[PROOF_A..PROOF_A + 64].copy_from_slice(proof_a);
+        pairing_input[PROOF_B..PROOF_B + 128].copy_from_slice(proof_b);
+        pairing_input[PREPARED_PUBLIC_INPUTS..PREPARED_PUBLIC_INPUTS + 64]
+            .copy_from_slice(&prepared_public_inputs);
+        pairing_input[PROOF_C..PROOF_C + 64].copy_from_slice(proof_c);
+
+        let pairing_res =
+            pairing(&pairing_input).map_err(|_| Groth16Error::ProofVerificationFailed)?;
+
+        if pairing_res[31] != 1 {
+            return Err(Groth16Error::ProofVerificationFailed);
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,100 @@
+//! Fixed-size wrappers around the alt_bn128 group operations and point decompression.
+//!
+//! On-chain they call the syscalls directly into stack buffers, `solana_bn254` returns a
+//! freshly allocated `Vec` for every result. Off-chain they go through `solana_bn254`, or
+//! through arkworks with the `native` feature. All of them reject a pairing input that
+//! isn't a whole number of pairs, which `solana_bn254` handles differently across versions.
+
+pub(crate) use target_arch::*;
+
+#[cfg(target_os = "solana")]
+mod target_arch {
+    pub(crate) use solana_bn254::compression::prelude::{
+        alt_bn128_g1_decompress as g1_decompress, alt_bn128_g2_decompress as g2_decompress,
+    };
+    use solana_bn254::prelude::*;
+
+    fn group_op<const N: usize>(op: u64, input: &[u8]) -> Result<[u8; N], AltBn128Error> {
+        let mut result_buffer = [0u8; N];
+        let result = unsafe {
+            solana_define_syscall::definitions::sol_alt_bn128_group_op(
+                op,
+                input.as_ptr(),
+                input.len() as u64,
+                result_buffer.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            0 => Ok(result_buffer),
+            _ => Err(AltBn128Error::UnexpectedError),
+        }
+    }
+
+    pub(crate) fn g1_addition(
+        input: &[u8; ALT_BN128_ADDITION_INPUT_LEN],
+    ) -> Result<[u8; ALT_BN128_ADDITION_OUTPUT_LEN], AltBn128Error> {
+        group_op(ALT_BN128_ADD, input)
+    }
+
+    pub(crate) fn g1_multiplication(
+        input: &[u8; ALT_BN128_MULTIPLICATION_INPUT_LEN],
+    ) -> Result<[u8; ALT_BN128_MULTIPLICATION_OUTPUT_LEN], AltBn128Error> {
+        group_op(ALT_BN128_MUL, input)
+    }
+
+    pub(crate) fn pairing(
+        input: &[u8],
+    ) -> Result<[u8; ALT_BN128_PAIRING_OUTPUT_LEN], AltBn128Error> {
+        if !input
+            .chunks_exact(ALT_BN128_PAIRING_ELEMENT_LEN)
+            .remainder()
+            .is_empty()
+        {
+            return Err(AltBn128Error::SliceOutOfBounds);
+        }
+        group_op(ALT_BN128_PAIRING, input)
+    }
+}
+
+#[cfg(all(not(target_os = "solana"), not(feature = "native")))]
+mod target_arch {
+    pub(crate) use solana_bn254::compression::prelude::{
+        alt_bn128_g1_decompress as g1_decompress, alt_bn128_g2_decompress as g2_decompress,
+    };
+    use solana_bn254::prelude::*;
+
+    pub(crate) fn g1_addition(
+        input: &[u8; ALT_BN128_ADDITION_INPUT_LEN],
+    ) -> Result<[u8; ALT_BN128_ADDITION_OUTPUT_LEN], AltBn128Error> {
+        alt_bn128_addition(input)?
+            .try_into()
+            .map_err(AltBn128Error::TryIntoVecError)
+    }
+
+    pub(crate) fn g1_multiplication(
+        input: &[u8; ALT_BN128_MULTIPLICATION_INPUT_LEN],
+    ) -> Result<[u8; ALT_BN128_MULTIPLICATION_OUTPUT_LEN], AltBn128Error> {
+        alt_bn128_multiplication(input)?
+            .try_into()
+            .map_err(AltBn128Error::TryIntoVecError)
+    }
+
+    pub(crate) fn pairing(
+        input: &[u8],
+    ) -> Result<[u8; ALT_BN128_PAIRING_OUTPUT_LEN], AltBn128Error> {
+        if !input
+            .chunks_exact(ALT_BN128_PAIRING_ELEMENT_LEN)
+            .remainder()
+            .is_empty()
+        {
+            return Err(AltBn128Error::SliceOutOfBounds);
+        }
+        alt_bn128_pairing(input)?
+            .try_into()
+            .map_err(AltBn128Error::TryIntoVecError)
+    }
+}
+
+#[cfg(all(not(target_os = "solana"), feature = "native"))]
+use crate::native as target_arch;
@@ -0,0 +1,597 @@
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Groth16, Proof, VerifyingKey};
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+};
+use ark_serialize::CanonicalSerialize;
+use ark_snark::SNARK;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use std::ops::Neg;
+
+use crate::{
+    errors::Groth16Error, is_less_than_bn254_field_size_be, Groth16BatchVerifier, Groth16Verifier,
+    Groth16Verifyingkey, PreparedVerifyingKey,
+};
+
+/// Proves knowledge of `a` and `b` with `a * b = product` and `a + b = sum`
+#[derive(Clone)]
+struct ProductSumCircuit {
+    a: Option<Fr>,
+    b: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for ProductSumCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let a = cs.new_witness_variable(|| self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.new_witness_variable(|| self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let product = cs.new_input_variable(|| {
+            Ok(self.a.ok_or(SynthesisError::AssignmentMissing)?
+                * self.b.ok_or(SynthesisError::AssignmentMissing)?)
+        })?;
+        let sum = cs.new_input_variable(|| {
+            Ok(self.a.ok_or(SynthesisError::AssignmentMissing)?
+                + self.b.ok_or(SynthesisError::AssignmentMissing)?)
+        })?;
+        cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + product)?;
+        cs.enforce_constraint(lc!() + a + b, lc!() + Variable::One, lc!() + sum)?;
+
+        Ok(())
+    }
+}
+
+struct SyscallProof {
+    proof_a: [u8; 64],
+    proof_b: [u8; 128],
+    proof_c: [u8; 64],
+    /// `proof_a`, `proof_b` and `proof_c` compressed like `prepare_proofs` in the wasm crate
+    compressed_a: [u8; 32],
+    compressed_b: [u8; 64],
+    compressed_c: [u8; 32],
+    public_inputs: [[u8; 32]; 2],
+}
+
+struct SyscallVerifyingKey {
+    vk_alpha_g1: [u8; 64],
+    vk_beta_g2: [u8; 128],
+    vk_gamme_g2: [u8; 128],
+    vk_delta_g2: [u8; 128],
+    vk_ic: Vec<[u8; 64]>,
+}
+
+impl SyscallVerifyingKey {
+    fn groth16_verifyingkey(&self) -> Groth16Verifyingkey<'_> {
+        Groth16Verifyingkey {
+            nr_pubinputs: self.vk_ic.len() - 1,
+            vk_alpha_g1: self.vk_alpha_g1,
+            vk_beta_g2: self.vk_beta_g2,
+            vk_gamme_g2: self.vk_gamme_g2,
+            vk_delta_g2: self.vk_delta_g2,
+            vk_ic: &self.vk_ic,
+        }
+    }
+}
+
+fn fr_to_bytes(fr: &Fr) -> [u8; 32] {
+    fr.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn g1_to_bytes(point: &G1Affine) -> [u8; 64] {
+    [
+        point.x.into_bigint().to_bytes_be(),
+        point.y.into_bigint().to_bytes_be(),
+    ]
+    .concat()
+    .try_into()
+    .unwrap()
+}
+
+fn g2_to_bytes(point: &G2Affine) -> [u8; 128] {
+    [
+        point.x.c1.into_bigint().to_bytes_be(),
+        point.x.c0.into_bigint().to_bytes_be(),
+        point.y.c1.into_bigint().to_bytes_be(),
+        point.y.c0.into_bigint().to_bytes_be(),
+    ]
+    .concat()
+    .try_into()
+    .unwrap()
+}
+
+/// Arkworks' compressed encoding reversed to big-endian, the layout of the decompression
+/// syscalls
+fn compress<P: CanonicalSerialize, const N: usize>(point: &P) -> [u8; N] {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes.reverse();
+    bytes.try_into().unwrap()
+}
+
+fn syscall_verifying_key(vk: &VerifyingKey<Bn254>) -> SyscallVerifyingKey {
+    SyscallVerifyingKey {
+        vk_alpha_g1: g1_to_bytes(&vk.alpha_g1),
+        vk_beta_g2: g2_to_bytes(&vk.beta_g2),
+        vk_gamme_g2: g2_to_bytes(&vk.gamma_g2),
+        vk_delta_g2: g2_to_bytes(&vk.delta_g2),
+        vk_ic: vk.gamma_abc_g1.iter().map(g1_to_bytes).collect(),
+    }
+}
+
+fn syscall_proof(proof: &Proof<Bn254>, public_inputs: &[Fr; 2]) -> SyscallProof {
+    SyscallProof {
+        proof_a: g1_to_bytes(&proof.a.neg()),
+        proof_b: g2_to_bytes(&proof.b),
+        proof_c: g1_to_bytes(&proof.c),
+        compressed_a: compress(&proof.a.neg()),
+        compressed_b: compress(&proof.b),
+        compressed_c: compress(&proof.c),
+        public_inputs: public_inputs.map(|input| fr_to_bytes(&input)),
+    }
+}
+
+/// Verifying key and `n` valid proofs of `ProductSumCircuit`
+fn setup(n: u64) -> (SyscallVerifyingKey, Vec<SyscallProof>) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let (pk, vk) =
+        Groth16::<Bn254>::circuit_specific_setup(ProductSumCircuit { a: None, b: None }, &mut rng)
+            .unwrap();
+
+    let proofs = (0..n)
+        .map(|i| {
+            let (a, b) = (Fr::from(3 + i), Fr::from(5 + 2 * i));
+            let proof = Groth16::<Bn254>::prove(
+                &pk,
+                ProductSumCircuit {
+                    a: Some(a),
+                    b: Some(b),
+                },
+                &mut rng,
+            )
+            .unwrap();
+            syscall_proof(&proof, &[a * b, a + b])
+        })
+        .collect();
+
+    (syscall_verifying_key(&vk), proofs)
+}
+
+#[test]
+fn test_verify() {
+    let (vk, proofs) = setup(1);
+    let verifyingkey = vk.groth16_verifyingkey();
+    let proof = &proofs[0];
+
+    let mut verifier = Groth16Verifier::new(
+        &proof.proof_a,
+        &proof.proof_b,
+        &proof.proof_c,
+        &proof.public_inputs,
+        &verifyingkey,
+    )
+    .unwrap();
+    assert_eq!(verifier.verify(), Ok(true));
+
+    let mut public_inputs = proof.public_inputs;
+    public_inputs[1][31] ^= 1;
+    let mut verifier = Groth16Verifier::new(
+        &proof.proof_a,
+        &proof.proof_b,
+        &proof.proof_c,
+        &public_inputs,
+        &verifyingkey,
+    )
+    .unwrap();
+    assert_eq!(
+        verifier.verify(),
+        Err(Groth16Error::ProofVerificationFailed)
+    );
+}
+
+#[test]
+fn test_verify_compressed() {
+    let (vk, proofs) = setup(2);
+    let verifyingkey = vk.groth16_verifyingkey();
+
+    let mut batch = Groth16BatchVerifier::<2>::new(&verifyingkey).unwrap();
+    let prepared = PreparedVerifyingKey::<2>::new(&verifyingkey).unwrap();
+    for proof in &proofs {
+        let mut verifier = Groth16Verifier::new_compressed(
+            &proof.compressed_a,
+            &proof.compressed_b,
+            &proof.compressed_c,
+            &proof.public_inputs,
+            &verifyingkey,
+        )
+        .unwrap();
+        assert_eq!(verifier.verify(), Ok(true));
+        assert_eq!(
+            prepared.verify_compressed(
+                &proof.compressed_a,
+                &proof.compressed_b,
+                &proof.compressed_c,
+                &proof.public_inputs
+            ),
+            Ok(true)
+        );
+        batch
+            .push_compressed(
+                &proof.compressed_a,
+                &proof.compressed_b,
+                &proof.compressed_c,
+                &proof.public_inputs,
+            )
+            .unwrap();
+    }
+    assert_eq!(batch.verify(), Ok(true));
+}
+
+#[test]
+fn test_verify_compressed_rejects_corrupted_points() {
+    let (vk, proofs) = setup(1);
+    let verifyingkey = vk.groth16_verifyingkey();
+    let proof = &proofs[0];
+
+    // Every x coordinate has a y on the curve for half of the field, the other half fail
+    // to decompress
+    let mut compressed_a = proof.compressed_a;
+    while Groth16Verifier::new_compressed(
+        &compressed_a,
+        &proof.compressed_b,
+        &proof.compressed_c,
+        &proof.public_inputs,
+        &verifyingkey,
+    )
+    .is_ok()
+    {
+        compressed_a[31] = compressed_a[31].wrapping_add(1);
+    }
+    assert_eq!(
+        Groth16Verifier::new_compressed(
+            &compressed_a,
+            &proof.compressed_b,
+            &proof.compressed_c,
+            &proof.public_inputs,
+            &verifyingkey,
+        ),
+        Err(Groth16Error::DecompressingG1Failed)
+    );
+
+    let mut compressed_b = proof.compressed_b;
+    compressed_b[1..].fill(0xff);
+    assert_eq!(
+        Groth16Verifier::new_compressed(
+            &proof.compressed_a,
+            &compressed_b,
+            &proof.compressed_c,
+            &proof.public_inputs,
+            &verifyingkey,
+        ),
+        Err(Groth16Error::DecompressingG2Failed)
+    );
+
+    // A valid point that isn't the proof's
+    let mut compressed_c = proof.compressed_c;
+    compressed_c[0] ^= 0x80;
+    let mut verifier = Groth16Verifier::new_compressed(
+        &proof.compressed_a,
+        &proof.compressed_b,
+        &compressed_c,
+        &proof.public_inputs,
+        &verifyingkey,
+    )
+    .unwrap();
+    assert_eq!(
+        verifier.verify(),
+        Err(Groth16Error::ProofVerificationFailed)
+    );
+}
+
+#[test]
+fn test_batch_verify() {
+    let (vk, proofs) = setup(4);
+    let verifyingkey = vk.groth16_verifyingkey();
+
+    let mut batch = Groth16BatchVerifier::<2>::new(&verifyingkey).unwrap();
+    assert_eq!(batch.verify(), Err(Groth16Error::EmptyBatch));
+
+    for proof in &proofs {
+        batch.push(
+            &proof.proof_a,
+            &proof.proof_b,
+            &proof.proof_c,
+            &proof.public_inputs,
+        );
+    }
+    assert_eq!(batch.len(), 4);
+    assert_eq!(batch.verify(), Ok(true));
+}
+
+#[test]
+fn test_batch_verify_rejects_invalid_proof() {
+    let (vk, proofs) = setup(3);
+    let verifyingkey = vk.groth16_verifyingkey();
+
+    // Valid proof bound to the inputs of another one
+    let mut batch = Groth16BatchVerifier::<2>::new(&verifyingkey).unwrap();
+    for (i, proof) in proofs.iter().enumerate() {
+        let public_inputs = if i == 1 {
+            &proofs[0].public_inputs
+        } else {
+            &proof.public_inputs
+        };
+        batch.push(
+            &proof.proof_a,
+            &proof.proof_b,
+            &proof.proof_c,
+            public_inputs,
+        );
+    }
+    assert_eq!(batch.verify(), Err(Groth16Error::ProofVerificationFailed));
+}
+
+#[test]
+fn test_batch_verifier_rejects_mismatched_verifying_key() {
+    let (vk, _) = setup(0);
+    let verifyingkey = vk.groth16_verifyingkey();
+
+    assert_eq!(
+        Groth16BatchVerifier::<3>::new(&verifyingkey),
+        Err(Groth16Error::IncompatibleVerifyingKeyWithNrPublicInputs)
+    );
+    assert_eq!(
+        Groth16BatchVerifier::<2>::new(&Groth16Verifyingkey {
+            vk_ic: &vk.vk_ic[..2],
+            ..vk.groth16_verifyingkey()
+        }),
+        Err(Groth16Error::InvalidPublicInputsLength)
+    );
+}
+
+#[test]
+fn test_is_less_than_bn254_field_size_be() {
+    let modulus: [u8; 32] = Fr::MODULUS.to_bytes_be().try_into().unwrap();
+    let mut below = modulus;
+    below[31] -= 1;
+
+    assert!(is_less_than_bn254_field_size_be(&[0u8; 32]));
+    assert!(is_less_than_bn254_field_size_be(&below));
+    assert!(!is_less_than_bn254_field_size_be(&modulus));
+    assert!(!is_less_than_bn254_field_size_be(&[0xff; 32]));
+}
+
+#[test]
+fn test_prepared_verify() {
+    let (vk, proofs) = setup(2);
+    let prepared = PreparedVerifyingKey::<2>::new(&vk.groth16_verifyingkey()).unwrap();
+
+    for proof in &proofs {
+        assert_eq!(
+            prepared.verify(
+                &proof.proof_a,
+                &proof.proof_b,
+                &proof.proof_c,
+                &proof.public_inputs
+            ),
+            Ok(true)
+        );
+    }
+
+    assert_eq!(
+        prepared.verify(
+            &proofs[0].proof_a,
+            &proofs[0].proof_b,
+            &proofs[0].proof_c,
+            &proofs[1].public_inputs
+        ),
+        Err(Groth16Error::ProofVerificationFailed)
+    );
+}
+
+#[test]
+fn test_prepared_verifying_key_rejects_malformed_keys() {
+    let (vk, _) = setup(0);
+    let verifyingkey = vk.groth16_verifyingkey();
+
+    assert_eq!(
+        PreparedVerifyingKey::<3>::new(&verifyingkey),
+        Err(Groth16Error::IncompatibleVerifyingKeyWithNrPublicInputs)
+    );
+    assert_eq!(
+        PreparedVerifyingKey::<2>::new(&Groth16Verifyingkey {
+            vk_ic: &vk.vk_ic[..2],
+            ..vk.groth16_verifyingkey()
+        }),
+        Err(Groth16Error::InvalidPublicInputsLength)
+    );
+
+    let mut vk_ic = vk.vk_ic.clone();
+    vk_ic[1] = [0u8; 64];
+    assert_eq!(
+        PreparedVerifyingKey::<2>::new(&Groth16Verifyingkey {
+            vk_ic: &vk_ic,
+            ..vk.groth16_verifyingkey()
+        }),
+        Err(Groth16Error::InvalidVerifyingKeyPoint)
+    );
+
+    let mut vk_alpha_g1 = vk.vk_alpha_g1;
+    vk_alpha_g1[63] ^= 1;
+    assert_eq!(
+        PreparedVerifyingKey::<2>::new(&Groth16Verifyingkey {
+            vk_alpha_g1,
+            ..vk.groth16_verifyingkey()
+        }),
+        Err(Groth16Error::InvalidVerifyingKeyPoint)
+    );
+
+    let mut vk_delta_g2 = vk.vk_delta_g2;
+    vk_delta_g2[127] ^= 1;
+    assert_eq!(
+        PreparedVerifyingKey::<2>::new(&Groth16Verifyingkey {
+            vk_delta_g2,
+            ..vk.groth16_verifyingkey()
+        }),
+        Err(Groth16Error::InvalidVerifyingKeyPoint)
+    );
+    assert!(
+        PreparedVerifyingKey::<2>::new_unchecked(&Groth16Verifyingkey {
+            vk_delta_g2,
+            ..vk.groth16_verifyingkey()
+        })
+        .is_ok()
+    );
+}
+
+/// The arkworks backend must agree with `solana_bn254` on every input, valid or not
+#[cfg(feature = "native")]
+mod native {
+    use ark_bn254::{Fq, Fq2, G1Projective};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use solana_bn254::{compression::prelude::*, prelude::*};
+
+    use super::*;
+    use crate::native;
+
+    fn g1_points() -> Vec<[u8; 64]> {
+        let generator = G1Affine::generator();
+        let point: G1Affine = (generator * Fr::from(7u64)).into_affine();
+        let mut off_curve = g1_to_bytes(&point);
+        off_curve[63] ^= 1;
+        let mut x_above_modulus = g1_to_bytes(&point);
+        x_above_modulus[..32].copy_from_slice(&Fq::MODULUS.to_bytes_be());
+        let mut flagged = g1_to_bytes(&point);
+        flagged[32] |= 0b0100_0000;
+
+        vec![
+            [0u8; 64],
+            g1_to_bytes(&generator),
+            g1_to_bytes(&point),
+            g1_to_bytes(&point.neg()),
+            off_curve,
+            x_above_modulus,
+            flagged,
+            [0xff; 64],
+        ]
+    }
+
+    fn g2_points() -> Vec<[u8; 128]> {
+        let generator = G2Affine::generator();
+        let point: G2Affine = (generator * Fr::from(11u64)).into_affine();
+        let mut off_curve = g2_to_bytes(&point);
+        off_curve[127] ^= 1;
+        // On the curve, outside of the prime order subgroup
+        let outside_subgroup = (1u64..)
+            .find_map(|x| G2Affine::get_point_from_x_unchecked(Fq2::from(x), true))
+            .unwrap();
+        assert!(!outside_subgroup.is_in_correct_subgroup_assuming_on_curve());
+
+        vec![
+            [0u8; 128],
+            g2_to_bytes(&generator),
+            g2_to_bytes(&point),
+            off_curve,
+            g2_to_bytes(&outside_subgroup),
+            [0xff; 128],
+        ]
+    }
+
+    fn scalars() -> Vec<[u8; 32]> {
+        vec![
+            [0u8; 32],
+            fr_to_bytes(&Fr::from(1u64)),
+            fr_to_bytes(&Fr::from(123_456_789u64).neg()),
+            Fr::MODULUS.to_bytes_be().try_into().unwrap(),
+            [0xff; 32],
+        ]
+    }
+
+    #[test]
+    fn test_g1_addition() {
+        for p in g1_points() {
+            for q in g1_points() {
+                let input: [u8; 128] = [p, q].concat().try_into().unwrap();
+                assert_eq!(
+                    native::g1_addition(&input).map(Vec::from),
+                    alt_bn128_addition(&input)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_g1_multiplication() {
+        for p in g1_points() {
+            for scalar in scalars() {
+                let input: [u8; 96] = [&p[..], &scalar[..]].concat().try_into().unwrap();
+                assert_eq!(
+                    native::g1_multiplication(&input).map(Vec::from),
+                    alt_bn128_multiplication(&input)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairing() {
+        let g1_generator = g1_to_bytes(&G1Affine::generator());
+        let g2_generator = g2_to_bytes(&G2Affine::generator());
+        // e(-g1, g2) * e(g1, g2) == 1
+        let valid = [
+            &g1_to_bytes(&G1Affine::generator().neg())[..],
+            &g2_generator[..],
+            &g1_generator[..],
+            &g2_generator[..],
+        ]
+        .concat();
+        assert_eq!(native::pairing(&valid).unwrap()[31], 1);
+
+        // Rejected by the syscall, whatever `alt_bn128_pairing` does with them
+        for len in [191, 193] {
+            assert_eq!(
+                native::pairing(&vec![0u8; len]),
+                Err(AltBn128Error::SliceOutOfBounds)
+            );
+        }
+
+        let mut inputs = vec![valid, vec![]];
+        for p in g1_points() {
+            for q in g2_points() {
+                inputs.push([&p[..], &q[..]].concat());
+            }
+        }
+        for input in inputs {
+            assert_eq!(
+                native::pairing(&input).map(Vec::from),
+                alt_bn128_pairing(&input)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decompress() {
+        let g1_points = [
+            G1Affine::generator(),
+            (G1Projective::from(G1Affine::generator()) * Fr::from(5u64)).into_affine(),
+        ];
+        let mut g1_inputs: Vec<[u8; 32]> = g1_points.iter().map(compress).collect();
+        g1_inputs.extend([[0u8; 32], [0xff; 32], [1u8; 32]]);
+        for input in g1_inputs {
+            assert_eq!(
+                native::g1_decompress(&input),
+                alt_bn128_g1_decompress(&input)
+            );
+        }
+
+        let g2_point: G2Affine = (G2Affine::generator() * Fr::from(3u64)).into_affine();
+        let mut g2_inputs: Vec<[u8; 64]> =
+            vec![compress(&G2Affine::generator()), compress(&g2_point)];
+        g2_inputs.extend([[0u8; 64], [0xff; 64], [1u8; 64]]);
+        for input in g2_inputs {
+            assert_eq!(
+                native::g2_decompress(&input),
+                alt_bn128_g2_decompress(&input)
+            );
+        }
+    }
+}
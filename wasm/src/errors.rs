@@ -23,4 +23,14 @@ pub enum ConversionError {
     ParseJsValueToRawProofError,
     #[error("failed to serialize PreparedProof")]
     SerializePreparedProofError,
+    #[error("failed to parse pubkey")]
+    ParsePubkeyError,
+    #[error("failed to parse JsValue to RawVerifyingKey")]
+    ParseJsValueToRawVerifyingKeyError,
+    #[error("failed to serialize PreparedVerifyingKey")]
+    SerializePreparedVerifyingKeyError,
+    #[error("field element overflows 32 bytes")]
+    FieldElementOverflow,
+    #[error("point has the wrong number of coordinates")]
+    InvalidPointLength,
 }
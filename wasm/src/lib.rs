@@ -4,7 +4,9 @@ pub mod generators;
 pub mod hashing;
 pub mod merkle;
 pub mod proof;
+pub mod public_inputs;
 mod rand;
 #[cfg(test)]
 mod tests;
 mod utils;
+pub mod verifying_key;
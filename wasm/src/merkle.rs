@@ -18,3 +18,15 @@ pub fn commitment_to_path(commitment: BigInt, depth: usize) -> Result<Uint8Array
 
     Ok(array)
 }
+
+/// Merkle path of a leaf of the on-chain incremental tree, top-to-bottom as the circuit expects it
+#[wasm_bindgen]
+pub fn leaf_index_to_path(leaf_index: u32, depth: usize) -> Uint8Array {
+    let array = Uint8Array::new_with_length(depth as u32);
+    for i in 0..depth {
+        let bit = (leaf_index >> (depth - 1 - i)) & 1;
+        array.set_index(i as u32, bit as u8);
+    }
+
+    array
+}
@@ -1,5 +1,6 @@
 use ark_bn254::{g1::G1Affine, Fq2, G2Affine};
 use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::CanonicalSerialize;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use std::{ops::Neg, str::FromStr};
@@ -49,6 +50,7 @@ interface PreparedProof {
     proof_b: number[];
     proof_c: number[];
     raw: number[];
+    compressed: number[];
 }
 "#;
 
@@ -64,6 +66,8 @@ pub struct PreparedProof {
     pub proof_b: Vec<u8>,
     pub proof_c: Vec<u8>,
     pub raw: Vec<u8>,
+    /// Compressed `raw`, 128 bytes, as taken by the redeem instructions
+    pub compressed: Vec<u8>,
 }
 
 impl PreparedProof {
@@ -73,6 +77,7 @@ impl PreparedProof {
             proof_b: Vec::new(),
             proof_c: Vec::new(),
             raw: vec![0; 256],
+            compressed: vec![0; 128],
         }
     }
 
@@ -87,6 +92,7 @@ impl PreparedProof {
 
         self.proof_a = convert_endianness_vec(g1_bytes.as_slice(), 32);
         self.raw.splice(0..64, self.proof_a.clone());
+        self.compressed.splice(0..32, compress(&g1));
     }
 
     pub fn set_proof_b(
@@ -118,6 +124,7 @@ impl PreparedProof {
         ]
         .concat();
         self.raw.splice(64..192, self.proof_b.clone());
+        self.compressed.splice(32..96, compress(&g2));
     }
 
     pub fn set_proof_c(&mut self, x_int: BigUint, y_int: BigUint) {
@@ -131,9 +138,19 @@ impl PreparedProof {
 
         self.proof_c = convert_endianness_vec(g1_bytes.as_slice(), 32);
         self.raw.splice(192..256, self.proof_c.clone());
+        self.compressed.splice(96..128, compress(&g1));
     }
 }
 
+/// Big-endian compressed point, the layout expected by the alt_bn128 decompression syscalls
+fn compress<P: CanonicalSerialize>(point: &P) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes.reverse();
+
+    bytes
+}
+
 impl Default for PreparedProof {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use js_sys::{Array, BigInt};
+use sha2::{Digest, Sha256};
+use solana_pubkey::Pubkey;
+use wasm_bindgen::prelude::*;
+
+use crate::{conversions::FrJsValue, errors::ConversionError};
+
+/// Split a pubkey into its high and low 16 bytes, matching the on-chain `pubkey_to_field_elements`
+pub fn pubkey_to_fr(pubkey: &Pubkey) -> [Fr; 2] {
+    let bytes = pubkey.to_bytes();
+
+    [
+        Fr::from_be_bytes_mod_order(&bytes[..16]),
+        Fr::from_be_bytes_mod_order(&bytes[16..]),
+    ]
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<Pubkey, ConversionError> {
+    Pubkey::from_str(pubkey).map_err(|_| ConversionError::ParsePubkeyError)
+}
+
+/// The `recipient` or `relayer` circuit inputs of a base58 pubkey
+#[wasm_bindgen]
+pub fn pubkey_to_field_elements(pubkey: String) -> Result<Array, JsError> {
+    let pubkey = parse_pubkey(&pubkey)?;
+
+    let array = Array::new();
+    for element in pubkey_to_fr(&pubkey) {
+        array.push(&element.to_js_bigint()?.into());
+    }
+
+    Ok(array)
+}
+
+fn public_inputs_to_fr(
+    root: BigInt,
+    nullifier_hash: BigInt,
+    recipient: &str,
+    relayer: &str,
+    fee: u64,
+    refund: u64,
+) -> Result<[Fr; 8], ConversionError> {
+    let [recipient_hi, recipient_lo] = pubkey_to_fr(&parse_pubkey(recipient)?);
+    let [relayer_hi, relayer_lo] = pubkey_to_fr(&parse_pubkey(relayer)?);
+
+    Ok([
+        Fr::from_js_bigint(root)?,
+        Fr::from_js_bigint(nullifier_hash)?,
+        recipient_hi,
+        recipient_lo,
+        relayer_hi,
+        relayer_lo,
+        Fr::from(fee),
+        Fr::from(refund),
+    ])
+}
+
+/// SHA-256 of the big-endian public inputs with the top 3 bits cleared, matching the on-chain
+/// `pack_public_inputs` and `KasbahPackedCommitmentCircuit`
+pub fn pack_public_inputs(inputs: &[Fr]) -> Fr {
+    let mut hasher = Sha256::new();
+    for input in inputs {
+        hasher.update(input.into_bigint().to_bytes_be());
+    }
+
+    let mut digest: [u8; 32] = hasher.finalize().into();
+    digest[0] &= 0b0001_1111;
+
+    Fr::from_be_bytes_mod_order(&digest)
+}
+
+/// Public inputs of `KasbahCommitmentCircuit` in the order the redeem instruction verifies them:
+/// `[root, nullifier_hash, recipient_hi, recipient_lo, relayer_hi, relayer_lo, fee, refund]`
+#[wasm_bindgen]
+pub fn redemption_public_inputs(
+    root: BigInt,
+    nullifier_hash: BigInt,
+    recipient: String,
+    relayer: String,
+    fee: u64,
+    refund: u64,
+) -> Result<Array, JsError> {
+    let inputs = public_inputs_to_fr(root, nullifier_hash, &recipient, &relayer, fee, refund)?;
+
+    let array = Array::new();
+    for input in inputs {
+        array.push(&input.to_js_bigint()?.into());
+    }
+
+    Ok(array)
+}
+
+/// `inputs_hash` of `KasbahPackedCommitmentCircuit`, for pools whose verifying key opted in
+#[wasm_bindgen]
+pub fn packed_redemption_public_input(
+    root: BigInt,
+    nullifier_hash: BigInt,
+    recipient: String,
+    relayer: String,
+    fee: u64,
+    refund: u64,
+) -> Result<BigInt, JsError> {
+    let inputs = public_inputs_to_fr(root, nullifier_hash, &recipient, &relayer, fee, refund)?;
+
+    Ok(pack_public_inputs(&inputs).to_js_bigint()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pubkey_to_fr() {
+        let pubkey = Pubkey::new_unique();
+        let [hi, lo] = pubkey_to_fr(&pubkey);
+
+        let hi = hi.into_bigint().to_bytes_be();
+        let lo = lo.into_bigint().to_bytes_be();
+
+        assert_eq!([&hi[16..], &lo[16..]].concat(), pubkey.to_bytes());
+    }
+
+    #[test]
+    fn test_pack_public_inputs() {
+        let inputs: Vec<Fr> = (0..8u64).map(Fr::from).collect();
+
+        // sha256(0 || 1 || ... || 7) as 32 bytes big-endian elements, top 3 bits cleared
+        let expected = Fr::from_str(
+            "9373754006623109483943029215117612623724104335287841978333478456755743788623",
+        )
+        .unwrap();
+
+        assert_eq!(pack_public_inputs(&inputs), expected);
+    }
+}
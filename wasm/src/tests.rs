@@ -1,17 +1,33 @@
-use ark_bn254::{Bn254, Fr};
+use ark_bn254::{
+    g1::{G1_GENERATOR_X, G1_GENERATOR_Y},
+    g2::{G2_GENERATOR_X, G2_GENERATOR_Y},
+    Bn254, Fr, G1Affine, G2Affine,
+};
 use ark_circom::{CircomBuilder, CircomConfig};
-use ark_ff::{AdditiveGroup, PrimeField};
+use ark_ff::{AdditiveGroup, BigInteger, PrimeField};
 use ark_groth16::Groth16;
 use ark_snark::SNARK;
 use ark_std::rand::thread_rng;
 use color_eyre::Result;
+use kasbah_groth16re::{Groth16Verifyingkey, PreparedVerifyingKey};
 use light_poseidon::{Poseidon, PoseidonHasher};
 use merkle_poseidon::SparseMerkleTree;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use solana_pubkey::Pubkey;
 
-use crate::{conversions::FrPathToVec, rand::random_fr};
+use crate::{
+    conversions::FrPathToVec,
+    errors::ConversionError,
+    proof::PreparedProof,
+    public_inputs::pubkey_to_fr,
+    rand::random_fr,
+    verifying_key::{PreparedVerifyingKey as RawPreparedVerifyingKey, RawVerifyingKey},
+};
 
 type GrothBn = Groth16<Bn254>;
 
+/// Depth of `ksb_2.circom`, the production `ksb.circom` is too large for a per-test setup
 const DEPTH: usize = 2;
 
 #[test]
@@ -29,9 +45,10 @@ fn test_poseidon_hash() -> Result<()> {
 }
 
 #[tokio::test]
+#[ignore = "needs circuits/ksb_2.{wasm,r1cs} from `npm run circuits:compile`"]
 async fn arkworks_wasm_compat_should_succeed() -> Result<()> {
     // Load circuit WASM and R1CS
-    let cfg = CircomConfig::<Fr>::new("../circuits/ksb.wasm", "../circuits/ksb.r1cs")?;
+    let cfg = CircomConfig::<Fr>::new("../circuits/ksb_2.wasm", "../circuits/ksb_2.r1cs")?;
 
     let mut builder = CircomBuilder::new(cfg);
     let mut hasher = Poseidon::<Fr>::new_circom(1)?;
@@ -60,6 +77,16 @@ async fn arkworks_wasm_compat_should_succeed() -> Result<()> {
     builder.push_input("root", root.into_bigint());
     builder.push_input("nullifier_hash", nullifier_hash.into_bigint());
 
+    // Redemption parameters
+    for value in pubkey_to_fr(&Pubkey::new_unique()) {
+        builder.push_input("recipient", value.into_bigint());
+    }
+    for value in pubkey_to_fr(&Pubkey::new_unique()) {
+        builder.push_input("relayer", value.into_bigint());
+    }
+    builder.push_input("fee", Fr::from(5_000u64).into_bigint());
+    builder.push_input("refund", Fr::ZERO.into_bigint());
+
     println!("root: {:?}", root.into_bigint());
     println!("nullifier_hash: {:?}", nullifier_hash.into_bigint());
 
@@ -99,5 +126,154 @@ async fn arkworks_wasm_compat_should_succeed() -> Result<()> {
 
     assert!(verified);
 
+    // Same proof in the redeem instruction encoding, checked like the program does
+    let mut prepared_proof = PreparedProof::new();
+    prepared_proof.set_proof_a(proof.a.x.into(), proof.a.y.into());
+    prepared_proof.set_proof_b(
+        proof.b.x.c0.into(),
+        proof.b.x.c1.into(),
+        proof.b.y.c0.into(),
+        proof.b.y.c1.into(),
+    );
+    prepared_proof.set_proof_c(proof.c.x.into(), proof.c.y.into());
+
+    let vk = &params.vk;
+    let vk_ic: Vec<[u8; 64]> = vk.gamma_abc_g1.iter().map(g1_to_be_bytes).collect();
+    let verifyingkey = Groth16Verifyingkey {
+        nr_pubinputs: inputs.len(),
+        vk_alpha_g1: g1_to_be_bytes(&vk.alpha_g1),
+        vk_beta_g2: g2_to_be_bytes(&vk.beta_g2),
+        vk_gamme_g2: g2_to_be_bytes(&vk.gamma_g2),
+        vk_delta_g2: g2_to_be_bytes(&vk.delta_g2),
+        vk_ic: &vk_ic,
+    };
+    let public_inputs: [[u8; 32]; 8] = inputs
+        .iter()
+        .map(|input| input.into_bigint().to_bytes_be().try_into().unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+
+    let compressed = &prepared_proof.compressed;
+    let verified = PreparedVerifyingKey::<8>::new(&verifyingkey)?.verify_compressed(
+        compressed[0..32].try_into()?,
+        compressed[32..96].try_into()?,
+        compressed[96..128].try_into()?,
+        &public_inputs,
+    )?;
+
+    assert!(verified);
+
     Ok(())
 }
+
+fn g1_to_be_bytes(point: &G1Affine) -> [u8; 64] {
+    [
+        point.x.into_bigint().to_bytes_be(),
+        point.y.into_bigint().to_bytes_be(),
+    ]
+    .concat()
+    .try_into()
+    .unwrap()
+}
+
+/// Coordinates ordered `(c1, c0)`
+fn g2_to_be_bytes(point: &G2Affine) -> [u8; 128] {
+    [
+        point.x.c1.into_bigint().to_bytes_be(),
+        point.x.c0.into_bigint().to_bytes_be(),
+        point.y.c1.into_bigint().to_bytes_be(),
+        point.y.c0.into_bigint().to_bytes_be(),
+    ]
+    .concat()
+    .try_into()
+    .unwrap()
+}
+
+fn generators() -> (G1Affine, G2Affine) {
+    (
+        G1Affine::new(G1_GENERATOR_X, G1_GENERATOR_Y),
+        G2Affine::new(G2_GENERATOR_X, G2_GENERATOR_Y),
+    )
+}
+
+fn decimal<F: PrimeField>(value: F) -> String {
+    value.into_bigint().to_string()
+}
+
+/// snarkjs export of a single public input key, every point a generator
+fn raw_verifying_key() -> RawVerifyingKey {
+    let (g1, g2) = generators();
+    let raw_g1 = || vec![decimal(g1.x), decimal(g1.y), "1".to_string()];
+    let raw_g2 = || {
+        vec![
+            vec![decimal(g2.x.c0), decimal(g2.x.c1)],
+            vec![decimal(g2.y.c0), decimal(g2.y.c1)],
+            vec!["1".to_string(), "0".to_string()],
+        ]
+    };
+
+    RawVerifyingKey {
+        n_public: 1,
+        vk_alpha_1: raw_g1(),
+        vk_beta_2: raw_g2(),
+        vk_gamma_2: raw_g2(),
+        vk_delta_2: raw_g2(),
+        ic: vec![raw_g1(), raw_g1()],
+    }
+}
+
+#[test]
+fn test_prepare_verifying_key() {
+    let (g1, g2) = generators();
+    let (g1, g2) = (g1_to_be_bytes(&g1), g2_to_be_bytes(&g2));
+
+    let prepared = RawPreparedVerifyingKey::try_from(raw_verifying_key()).unwrap();
+
+    assert_eq!(prepared.vk_alpha_g1, g1);
+    assert_eq!(prepared.vk_beta_g2, g2);
+    assert_eq!(prepared.vk_gamma_g2, g2);
+    assert_eq!(prepared.vk_delta_g2, g2);
+    assert_eq!(prepared.vk_ic, vec![g1.to_vec(), g1.to_vec()]);
+    // Same as `VerifyingKeyAccount::hash` on-chain
+    let hash = Sha256::digest([&g1[..], &g2, &g2, &g2, &g1, &g1].concat());
+    assert_eq!(prepared.hash, hash.to_vec());
+}
+
+#[test]
+fn test_prepare_verifying_key_rejects_malformed_points() {
+    let mut raw_vk = raw_verifying_key();
+    raw_vk.vk_alpha_1.truncate(1);
+    assert!(matches!(
+        RawPreparedVerifyingKey::try_from(raw_vk),
+        Err(ConversionError::InvalidPointLength)
+    ));
+
+    let mut raw_vk = raw_verifying_key();
+    raw_vk.vk_delta_2.truncate(1);
+    assert!(matches!(
+        RawPreparedVerifyingKey::try_from(raw_vk),
+        Err(ConversionError::InvalidPointLength)
+    ));
+
+    let mut raw_vk = raw_verifying_key();
+    raw_vk.vk_beta_2[1].truncate(1);
+    assert!(matches!(
+        RawPreparedVerifyingKey::try_from(raw_vk),
+        Err(ConversionError::InvalidPointLength)
+    ));
+
+    let mut raw_vk = raw_verifying_key();
+    raw_vk.ic.pop();
+    assert!(matches!(
+        RawPreparedVerifyingKey::try_from(raw_vk),
+        Err(ConversionError::InvalidPointLength)
+    ));
+
+    let mut raw_vk = raw_verifying_key();
+    raw_vk.ic[1][0] = BigUint::from(2u8).pow(256).to_string();
+    assert!(matches!(
+        RawPreparedVerifyingKey::try_from(raw_vk),
+        Err(ConversionError::FieldElementOverflow)
+    ));
+}
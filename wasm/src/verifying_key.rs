@@ -0,0 +1,177 @@
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+use crate::errors::ConversionError;
+
+#[wasm_bindgen(typescript_custom_section)]
+const RAW_VERIFYING_KEY_INTERFACE: &'static str = r#"
+interface RawVerifyingKey {
+    nPublic: number;
+    vk_alpha_1: string[];
+    vk_beta_2: string[][];
+    vk_gamma_2: string[][];
+    vk_delta_2: string[][];
+    IC: string[][];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RawVerifyingKey")]
+    pub type RawVerifyingKeyInterface;
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RawVerifyingKey {
+    #[serde(rename = "nPublic")]
+    pub n_public: usize,
+    pub vk_alpha_1: Vec<String>,
+    pub vk_beta_2: Vec<Vec<String>>,
+    pub vk_gamma_2: Vec<Vec<String>>,
+    pub vk_delta_2: Vec<Vec<String>>,
+    #[serde(rename = "IC")]
+    pub ic: Vec<Vec<String>>,
+}
+
+impl TryFrom<JsValue> for RawVerifyingKey {
+    type Error = ConversionError;
+
+    fn try_from(raw_vk: JsValue) -> Result<Self, Self::Error> {
+        serde_wasm_bindgen::from_value(raw_vk)
+            .map_err(|_| ConversionError::ParseJsValueToRawVerifyingKeyError)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const PREPARED_VERIFYING_KEY_INTERFACE: &'static str = r#"
+interface PreparedVerifyingKey {
+    vk_alpha_g1: number[];
+    vk_beta_g2: number[];
+    vk_gamma_g2: number[];
+    vk_delta_g2: number[];
+    vk_ic: number[][];
+    hash: number[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "PreparedVerifyingKey")]
+    pub type PreparedVerifyingKeyInterface;
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PreparedVerifyingKey {
+    pub vk_alpha_g1: Vec<u8>,
+    pub vk_beta_g2: Vec<u8>,
+    pub vk_gamma_g2: Vec<u8>,
+    pub vk_delta_g2: Vec<u8>,
+    pub vk_ic: Vec<Vec<u8>>,
+    /// `VerifyingKeyAccount::hash` once uploaded, the `vk_hash` of `CreatePoolInput`
+    pub hash: Vec<u8>,
+}
+
+impl TryFrom<RawVerifyingKey> for PreparedVerifyingKey {
+    type Error = ConversionError;
+
+    fn try_from(raw_vk: RawVerifyingKey) -> Result<Self, Self::Error> {
+        if raw_vk.ic.len() != raw_vk.n_public + 1 {
+            return Err(ConversionError::InvalidPointLength);
+        }
+
+        let vk_alpha_g1 = g1_to_bytes(&raw_vk.vk_alpha_1)?;
+        let vk_beta_g2 = g2_to_bytes(&raw_vk.vk_beta_2)?;
+        let vk_gamma_g2 = g2_to_bytes(&raw_vk.vk_gamma_2)?;
+        let vk_delta_g2 = g2_to_bytes(&raw_vk.vk_delta_2)?;
+        let vk_ic: Vec<Vec<u8>> = raw_vk
+            .ic
+            .iter()
+            .map(|point| g1_to_bytes(point))
+            .collect::<Result<_, _>>()?;
+
+        let mut hasher = Sha256::new();
+        for point in [&vk_alpha_g1, &vk_beta_g2, &vk_gamma_g2, &vk_delta_g2]
+            .into_iter()
+            .chain(&vk_ic)
+        {
+            hasher.update(point);
+        }
+
+        Ok(Self {
+            vk_alpha_g1,
+            vk_beta_g2,
+            vk_gamma_g2,
+            vk_delta_g2,
+            vk_ic,
+            hash: hasher.finalize().to_vec(),
+        })
+    }
+}
+
+impl TryInto<JsValue> for PreparedVerifyingKey {
+    type Error = ConversionError;
+
+    fn try_into(self) -> Result<JsValue, Self::Error> {
+        serde_wasm_bindgen::to_value(&self)
+            .map_err(|_| ConversionError::SerializePreparedVerifyingKeyError)
+    }
+}
+
+/// Decimal string to a 32 bytes big-endian field element
+fn field_to_bytes(value: &str) -> Result<Vec<u8>, ConversionError> {
+    let value = BigUint::from_str(value)
+        .map_err(|_| ConversionError::ParseJsValueToRawVerifyingKeyError)?;
+    let bytes = value.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(ConversionError::FieldElementOverflow);
+    }
+
+    let mut element = vec![0u8; 32 - bytes.len()];
+    element.extend_from_slice(&bytes);
+    Ok(element)
+}
+
+/// `[x, y, "1"]` to `x || y`
+fn g1_to_bytes(point: &[String]) -> Result<Vec<u8>, ConversionError> {
+    let [x, y, ..] = point else {
+        return Err(ConversionError::InvalidPointLength);
+    };
+
+    Ok([field_to_bytes(x)?, field_to_bytes(y)?].concat())
+}
+
+/// `[[x_c0, x_c1], [y_c0, y_c1], ["1", "0"]]` to `x_c1 || x_c0 || y_c1 || y_c0`
+fn g2_to_bytes(point: &[Vec<String>]) -> Result<Vec<u8>, ConversionError> {
+    let [x, y, ..] = point else {
+        return Err(ConversionError::InvalidPointLength);
+    };
+    let ([x_c0, x_c1, ..], [y_c0, y_c1, ..]) = (x.as_slice(), y.as_slice()) else {
+        return Err(ConversionError::InvalidPointLength);
+    };
+
+    Ok([
+        field_to_bytes(x_c1)?,
+        field_to_bytes(x_c0)?,
+        field_to_bytes(y_c1)?,
+        field_to_bytes(y_c0)?,
+    ]
+    .concat())
+}
+
+/// Convert a snarkjs `verification_key.json` into the layout of the on-chain
+/// `VerifyingKeyAccount`, ready for `init_verifying_key` and `append_verifying_key_ic`
+#[wasm_bindgen]
+pub fn prepare_verifying_key(
+    raw_vk: RawVerifyingKeyInterface,
+) -> Result<PreparedVerifyingKeyInterface, JsError> {
+    let raw_vk: JsValue = raw_vk.into();
+    let raw_vk: RawVerifyingKey = raw_vk.try_into()?;
+
+    let prepared_vk: PreparedVerifyingKey = raw_vk.try_into()?;
+    let prepared_vk: JsValue = prepared_vk.try_into()?;
+
+    Ok(prepared_vk.into())
+}